@@ -7,8 +7,11 @@ fn main() {
 
     println!("cargo:rustc-link-search=native={}", lib_dir.display());
 
-    // Link dynamically against required libraries
-    println!("cargo:rustc-link-lib=dylib=AppCore");
+    // Link dynamically against required libraries. AppCore is only needed for the
+    // `app` feature, so headless builds don't require it.
+    if app_enabled() {
+        println!("cargo:rustc-link-lib=dylib=AppCore");
+    }
     println!("cargo:rustc-link-lib=dylib=Ultralight");
     println!("cargo:rustc-link-lib=dylib=UltralightCore");
     println!("cargo:rustc-link-lib=dylib=WebCore");
@@ -37,12 +40,14 @@ fn find_ultralight_lib_dir() -> PathBuf {
     };
 
     // Library files to search for
-    let required_libs = [
+    let mut required_libs = vec![
         format!("libUltralight.{}", lib_file_ext),
-        format!("libAppCore.{}", lib_file_ext),
         format!("libUltralightCore.{}", lib_file_ext),
         format!("libWebCore.{}", lib_file_ext),
     ];
+    if app_enabled() {
+        required_libs.push(format!("libAppCore.{}", lib_file_ext));
+    }
 
     // First check UL_DIR environment variable
     if let Ok(dir) = env::var("UL_DIR") {
@@ -161,6 +166,11 @@ fn find_ultralight_lib_dir() -> PathBuf {
     );
 }
 
+/// Whether the `app` feature, which needs the AppCore library, is enabled.
+fn app_enabled() -> bool {
+    env::var_os("CARGO_FEATURE_APP").is_some()
+}
+
 fn has_required_libs(dir: &Path, libs: &[String]) -> bool {
     for lib in libs {
         if !dir.join(lib).exists() {
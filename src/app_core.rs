@@ -8,41 +8,42 @@
 //!
 //! ## Example usage
 //!
-//! ```rust
-//! use ultralight_rs::*;
-//! use ultralight_appcore::*;
+//! ```no_run
+//! use ul::*;
+//! use ul::app_core::*;
 //!
-//! fn main() {
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
 //!     // Create app settings with default values
-//!     let settings = Settings::new();
+//!     let settings = Settings::new()?;
 //!     
 //!     // Create ultralight config with default values
 //!     let config = Config::new();
 //!     
 //!     // Create the app with our settings and config
-//!     let app = App::new(&settings, &config);
+//!     let app = App::new(&settings, &config)?;
 //!     
 //!     // Create a window
 //!     let window = Window::new(
-//!         &app.main_monitor(),
+//!         &app.main_monitor()?,
 //!         800,
 //!         600,
 //!         false,
 //!         WindowFlags::TITLED | WindowFlags::RESIZABLE
-//!     );
+//!     )?;
 //!     
 //!     // Set window title
 //!     window.set_title("Ultralight AppCore Example");
 //!     
 //!     // Create an overlay (web view) that fills the window
-//!     let overlay = Overlay::new(&window, 800, 600, 0, 0);
+//!     let overlay = Overlay::new(&window, 800, 600, 0, 0)?;
 //!     
 //!     // Get the view from the overlay and load a URL
-//!     let view = overlay.view();
+//!     let view = overlay.view()?;
 //!     view.load_url("https://example.com");
 //!     
 //!     // Run the app
 //!     app.run();
+//!     Ok(())
 //! }
 //! ```
 
@@ -62,3 +63,4 @@ pub use self::monitor::Monitor;
 pub use self::overlay::Overlay;
 pub use self::settings::Settings;
 pub use self::window::{Window, WindowFlags};
+pub use crate::ul::Cursor;
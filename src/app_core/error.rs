@@ -10,6 +10,7 @@ pub enum Error {
     ResourceNotFound(&'static str),
     ResourceAllocationFailed(&'static str),
     CallbackRegistrationFailed(&'static str),
+    UnsupportedOperation(&'static str),
 }
 
 impl fmt::Display for Error {
@@ -22,6 +23,7 @@ impl fmt::Display for Error {
             Error::ResourceNotFound(desc) => write!(f, "Resource not found: {}", desc),
             Error::ResourceAllocationFailed(desc) => write!(f, "Resource allocation failed: {}", desc),
             Error::CallbackRegistrationFailed(desc) => write!(f, "Callback registration failed: {}", desc),
+            Error::UnsupportedOperation(desc) => write!(f, "Unsupported operation: {}", desc),
         }
     }
 }
@@ -2,6 +2,8 @@ use crate::app_core::ffi::{
     ulEnableDefaultLogger, ulEnablePlatformFileSystem, ulEnablePlatformFontLoader,
 };
 use crate::ul::String;
+use crate::ul::{logger, platform};
+use std::path::PathBuf;
 
 /// Initialize the platform font loader.
 ///
@@ -27,6 +29,7 @@ pub fn enable_platform_file_system(base_dir: &str) {
     unsafe {
         ulEnablePlatformFileSystem(base_dir_string.raw());
     }
+    platform::set_file_system_base_dir(Some(PathBuf::from(base_dir)));
 }
 
 /// Initialize the default logger.
@@ -38,8 +41,11 @@ pub fn enable_platform_file_system(base_dir: &str) {
 ///
 /// * `log_path` - A writable log path to write the log to (e.g., "./ultralight.log")
 pub fn enable_default_logger(log_path: &str) {
-    let log_path_string = String::from_str(log_path);
-    unsafe {
-        ulEnableDefaultLogger(log_path_string.raw());
-    }
-}
\ No newline at end of file
+    let log_path = log_path.to_owned();
+    logger::set_native_logger(move || {
+        let log_path = String::from_str(&log_path);
+        unsafe {
+            ulEnableDefaultLogger(log_path.raw());
+        }
+    });
+}
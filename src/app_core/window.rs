@@ -8,7 +8,7 @@ use crate::app_core::ffi::{
 };
 use crate::app_core::error::Error;
 use crate::app_core::monitor::Monitor;
-use crate::ul::Cursor;
+use crate::ul::{Bitmap, Cursor};
 use bitflags::bitflags;
 use std::ffi::CString;
 use std::os::raw::{c_void, c_uint};
@@ -253,6 +253,21 @@ impl Window {
         unsafe { ulWindowGetHeight(self.raw) }
     }
 
+    /// Get the client area size as `(width, height)` in pixels.
+    ///
+    /// AppCore has no call to resize a window after creation; the size can only
+    /// change through the user or the platform, reported via the resize callback.
+    pub fn client_size_pixels(&self) -> (u32, u32) {
+        (self.width(), self.height())
+    }
+
+    /// Get the client area size as `(width, height)` in screen coordinates.
+    ///
+    /// This is the pixel size divided by `scale()`.
+    pub fn client_size_screen(&self) -> (u32, u32) {
+        (self.screen_width(), self.screen_height())
+    }
+
     /// Move the window to a new position (in screen coordinates) relative to
     /// the top-left of the monitor area.
     pub fn move_to(&self, x: i32, y: i32) {
@@ -285,6 +300,25 @@ impl Window {
         unsafe { ulWindowIsFullscreen(self.raw) }
     }
 
+    /// Enter or leave fullscreen mode.
+    ///
+    /// AppCore only supports choosing fullscreen when the window is created and
+    /// has no runtime toggle (nor maximize/minimize/restore), so this succeeds
+    /// only if the window is already in the requested state.
+    ///
+    /// # Returns
+    ///
+    /// `Error::UnsupportedOperation` if the fullscreen state would change.
+    pub fn set_fullscreen(&self, fullscreen: bool) -> Result<(), Error> {
+        if self.is_fullscreen() == fullscreen {
+            Ok(())
+        } else {
+            Err(Error::UnsupportedOperation(
+                "Fullscreen can only be set when the window is created",
+            ))
+        }
+    }
+
     /// Get the DPI scale of the window.
     pub fn scale(&self) -> f64 {
         unsafe { ulWindowGetScale(self.raw) }
@@ -319,6 +353,35 @@ impl Window {
         }
     }
 
+    /// Apply a cursor requested by a view to the window.
+    ///
+    /// Views report cursor changes through `View::set_change_cursor_callback` but
+    /// have no window to apply them to. Call this with the reported cursor to show it
+    /// while the mouse is over the window.
+    pub fn apply_view_cursor(&self, cursor: Cursor) {
+        self.set_cursor(cursor);
+    }
+
+    /// Set the window icon.
+    ///
+    /// The AppCore C API has no way to set a window icon, so this always returns
+    /// `Error::UnsupportedOperation`. Use `native_handle` to set the icon through the
+    /// platform API instead, such as `WM_SETICON` on Windows or `glfwSetWindowIcon`
+    /// on Linux. On macOS, the icon comes from the application bundle.
+    pub fn set_icon(&self, _icon: &Bitmap) -> Result<(), Error> {
+        Err(Error::UnsupportedOperation("AppCore does not support setting the window icon"))
+    }
+
+    /// Set the minimum size of the window's client area, in screen coordinates.
+    ///
+    /// The AppCore C API has no way to constrain the window size, so this always
+    /// returns `Error::UnsupportedOperation`. Use `native_handle` to set it through
+    /// the platform API instead, such as handling `WM_GETMINMAXINFO` on Windows,
+    /// `glfwSetWindowSizeLimits` on Linux or `NSWindow.minSize` on macOS.
+    pub fn set_min_size(&self, _width: u32, _height: u32) -> Result<(), Error> {
+        Err(Error::UnsupportedOperation("AppCore does not support a minimum window size"))
+    }
+
     /// Show the window (if it was previously hidden).
     pub fn show(&self) {
         unsafe {
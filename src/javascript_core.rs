@@ -0,0 +1,25 @@
+//! JavaScriptCore bindings for Rust
+//! 
+//! This module provides safe, idiomatic Rust bindings to the JavaScriptCore C API.
+
+// Re-export the main components for a clean public API
+pub use context::{Context, ContextGroup, GlobalContext, LogLevel, Scope};
+pub use value::{ArrayIter, HashableValue, ProtectedValue, Value, ValueType, WeakValue};
+pub use object::{Object, Class, ClassDefinition, HostObject, PropertyAttributes, ClassAttributes, StaticFunction, StaticValue};
+pub use string::String;
+pub use typed_array::{ArrayBuffer, TypedArray, TypedArrayType};
+pub use regexp::RegExp;
+pub use date::Date;
+pub use exception::{Error, ProtectedException, Result};
+pub use function::{FromValue, IntoValue, TypedCallback, TypedFunction};
+
+pub mod ffi;
+mod context;
+mod value;
+mod object;
+mod string;
+mod typed_array;
+mod regexp;
+mod date;
+mod exception;
+mod function;
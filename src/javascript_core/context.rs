@@ -6,15 +6,18 @@
 //! a JavaScript execution environment with its own global object and execution state,
 //! while GlobalContext represents an owning reference to a context.
 
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::ptr;
-use std::ffi::{CStr, CString};
-use std::convert::TryFrom;
+use std::rc::Rc;
+use std::os::raw::c_void;
 
 use crate::javascript_core::ffi;
-use crate::javascript_core::error::{Error, Result};
-use crate::javascript_core::object::Object;
-use crate::javascript_core::value::Value;
+use crate::javascript_core::exception::{Error, Result};
+use crate::javascript_core::function::{TypedCallback, TypedFunction};
+use crate::javascript_core::object::{Object, PropertyAttributes};
+use crate::javascript_core::value::{ProtectedValue, Value};
 use crate::javascript_core::string::String;
 
 /// A reference to a JavaScript execution context.
@@ -23,10 +26,32 @@ use crate::javascript_core::string::String;
 /// environment for JavaScript code. Each Context has a global object and maintains
 /// the execution state. The Context is non-owning and therefore must not outlive
 /// the GlobalContext that created it.
+///
+/// JavaScriptCore contexts are single-threaded, so Context (and every Value or
+/// Object derived from it) is neither `Send` nor `Sync`.
+///
+/// # Lifetimes
+///
+/// The lifetime `'a` is the borrow of the GlobalContext the Context was obtained
+/// from, and every Value and Object created through it carries the same lifetime.
+/// The borrow checker therefore rejects any use of a value after its GlobalContext
+/// has been dropped. Contexts handed to callbacks are only valid for the duration
+/// of the call, so values received there cannot be stored beyond it either.
+///
+/// ```compile_fail,E0597
+/// use ul::javascript_core::{GlobalContext, Value};
+///
+/// let escaped: Value;
+/// {
+///     let global = GlobalContext::new();
+///     escaped = Value::number(&global.context(), 1.0);
+/// } // `global` dropped here while still borrowed
+/// let _ = escaped.to_number();
+/// ```
 #[derive(Clone)]
 pub struct Context<'a> {
     raw: ffi::JSContextRef,
-    _phantom: PhantomData<&'a ()>,
+    _phantom: PhantomData<(&'a (), *const ())>,
 }
 
 /// A global JavaScript execution context.
@@ -35,8 +60,12 @@ pub struct Context<'a> {
 /// top-level JavaScript execution environment. It manages the lifetime of the context
 /// and ensures proper cleanup when it's dropped. The GlobalContext can provide
 /// references to its contained Context for operations that require a context reference.
+///
+/// A GlobalContext must only be used from the thread that created it, so it is
+/// neither `Send` nor `Sync`.
 pub struct GlobalContext {
     raw: ffi::JSGlobalContextRef,
+    _phantom: PhantomData<*const ()>,
 }
 
 impl<'a> Context<'a> {
@@ -45,7 +74,9 @@ impl<'a> Context<'a> {
     /// # Safety
     ///
     /// The provided JSContextRef must be a valid pointer to a JavaScript context,
-    /// and must not be deallocated while this Context is alive.
+    /// and must not be deallocated while this Context is alive. The caller chooses
+    /// `'a`, so it must be bounded by a borrow of the owning GlobalContext or by the
+    /// scope of the callback that received `raw`; it must never be `'static`.
     pub(crate) unsafe fn from_raw(raw: ffi::JSContextRef) -> Self {
         Context {
             raw,
@@ -83,6 +114,84 @@ impl<'a> Context<'a> {
         }
     }
     
+    /// Sets a property on the global object.
+    ///
+    /// The property is created with `PropertyAttributes::NONE`, making it writable,
+    /// enumerable and deletable, as a plain assignment from JavaScript would.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the global.
+    /// * `value` - The value to assign.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error if the assignment threw.
+    pub fn set_global(&self, name: &str, value: Value<'a>) -> Result<()> {
+        self.global_object().set_property(name, value, PropertyAttributes::NONE)
+    }
+    
+    /// Gets a property from the global object.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the global.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the value, which is `undefined` if the global does
+    /// not exist, or an error if the lookup threw.
+    pub fn get_global(&self, name: &str) -> Result<Value<'a>> {
+        self.global_object().get_property(name)
+    }
+    
+    /// Creates a function backed by a Rust callback and installs it as a global.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the global function.
+    /// * `callback` - The Rust closure invoked when the function is called.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the created function object, or an error if it could
+    /// not be installed.
+    pub fn define_function<F>(&self, name: &str, callback: F) -> Result<Object<'a>>
+    where
+        F: for<'c> Fn(&Context<'c>, &Object<'c>, Option<&Object<'c>>, &[Value<'c>]) -> Result<Value<'c>> + 'static,
+    {
+        let function = Object::function_with_callback(self, Some(name), callback);
+        self.set_global(name, function.to_value())?;
+        Ok(function)
+    }
+    
+    /// Creates a function from a typed Rust closure and installs it as a global.
+    ///
+    /// The closure's parameters are converted from the JavaScript arguments with
+    /// FromValue and its return value back with IntoValue, as with TypedFunction:
+    ///
+    /// ```ignore
+    /// context.register_fn("add", |a: f64, b: f64| -> f64 { a + b })?;
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the global function.
+    /// * `callback` - The typed Rust closure invoked when the function is called.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the created function object, or an error if it could
+    /// not be installed.
+    pub fn register_fn<Args, F>(&self, name: &str, callback: F) -> Result<Object<'a>>
+    where
+        F: TypedCallback<Args>,
+    {
+        let function = TypedFunction::new(self, callback);
+        self.set_global(name, function.to_value())?;
+        Ok(function)
+    }
+    
     /// Returns the context group that this context belongs to.
     ///
     /// A context group associates JavaScript contexts with one another. Contexts in the
@@ -93,6 +202,21 @@ impl<'a> Context<'a> {
         }
     }
     
+    /// Checks whether this context and another belong to the same context group.
+    ///
+    /// Only contexts in the same group may exchange JavaScript values.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The context to compare with.
+    ///
+    /// # Returns
+    ///
+    /// `true` if both contexts share a context group, `false` otherwise.
+    pub fn shares_group_with(&self, other: &Context<'_>) -> bool {
+        self.group() == other.group()
+    }
+    
     /// Returns the global context that this context belongs to.
     ///
     /// A global context is the root context that owns the JavaScript environment.
@@ -142,8 +266,13 @@ impl<'a> Context<'a> {
                 &mut exception,
             );
             
+            if take_timed_out(self.group()) {
+                return Err(Error::JSError("execution timed out".to_string()));
+            }
+            
             if !exception.is_null() {
-                return Err(Error::from_js_exception(self.raw, exception));
+                let error = Error::from_js_exception(self.raw, exception);
+                return Err(with_script_position(error, source_url));
             }
             
             if result.is_null() {
@@ -158,7 +287,8 @@ impl<'a> Context<'a> {
     ///
     /// This method parses the provided JavaScript code to determine if it has valid
     /// syntax, without evaluating it. This is useful for validating user input
-    /// before execution.
+    /// before execution. A syntax error is returned as an `Error::JSException`
+    /// carrying the line and source URL of the error where they can be determined.
     ///
     /// # Arguments
     ///
@@ -190,7 +320,8 @@ impl<'a> Context<'a> {
             );
             
             if !exception.is_null() {
-                return Err(Error::from_js_exception(self.raw, exception));
+                let error = Error::from_js_exception(self.raw, exception);
+                return Err(with_script_position(error, source_url));
             }
             
             Ok(result)
@@ -210,6 +341,158 @@ impl<'a> Context<'a> {
             ffi::JSGarbageCollect(self.raw);
         }
     }
+    
+    /// Runs a closure with a scope that protects the values created through it.
+    ///
+    /// Every value created through the Scope is protected from garbage collection
+    /// until the closure returns, and then unprotected, so protection can't leak.
+    /// Values that must outlive the scope are kept with `Scope::escape`. This
+    /// mirrors the HandleScope pattern of V8.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The closure to run with the scope.
+    ///
+    /// # Returns
+    ///
+    /// The result of the closure.
+    pub fn scope<R>(&self, f: impl FnOnce(&Scope<'_, 'a>) -> R) -> R {
+        let scope = Scope {
+            context: self,
+            values: RefCell::new(Vec::new()),
+        };
+        f(&scope)
+    }
+}
+
+/// A scope that protects values from garbage collection until it ends.
+///
+/// Created by `Context::scope`. All values created or added through the scope
+/// are unprotected when the scope ends; escaped values stay protected for as long
+/// as the ProtectedValue returned by `escape` lives.
+pub struct Scope<'s, 'a> {
+    context: &'s Context<'a>,
+    values: RefCell<Vec<Value<'a>>>,
+}
+
+impl<'s, 'a> Scope<'s, 'a> {
+    /// Returns the context of this scope.
+    pub fn context(&self) -> &Context<'a> {
+        self.context
+    }
+    
+    /// Protects a value until the scope ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to protect.
+    ///
+    /// # Returns
+    ///
+    /// The same value.
+    pub fn add(&self, value: Value<'a>) -> Value<'a> {
+        value.protect();
+        self.values.borrow_mut().push(value.clone());
+        value
+    }
+    
+    /// Creates a number value that is protected until the scope ends.
+    pub fn number(&self, value: f64) -> Value<'a> {
+        self.add(Value::number(self.context, value))
+    }
+    
+    /// Creates a boolean value that is protected until the scope ends.
+    pub fn boolean(&self, value: bool) -> Value<'a> {
+        self.add(Value::boolean(self.context, value))
+    }
+    
+    /// Creates a string value that is protected until the scope ends.
+    pub fn string(&self, value: &str) -> Value<'a> {
+        self.add(Value::string(self.context, value))
+    }
+    
+    /// Creates an empty object that is protected until the scope ends.
+    pub fn object(&self) -> Object<'a> {
+        let object = Object::new(self.context);
+        self.add(object.to_value());
+        object
+    }
+    
+    /// Keeps a value alive past the end of the scope.
+    ///
+    /// The value is protected independently of the scope, so it remains protected
+    /// when the scope ends and is only unprotected when the returned ProtectedValue
+    /// is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to escape.
+    ///
+    /// # Returns
+    ///
+    /// A ProtectedValue owning the escaped value's protection.
+    pub fn escape(&self, value: Value<'a>) -> ProtectedValue<'a> {
+        ProtectedValue::new(value)
+    }
+}
+
+impl<'s, 'a> Drop for Scope<'s, 'a> {
+    fn drop(&mut self) {
+        for value in self.values.get_mut().drain(..) {
+            value.unprotect();
+        }
+    }
+}
+
+/// Fills in missing position information on an exception thrown by a script.
+///
+/// Syntax errors often lack `line` and `sourceURL` properties, so the line is taken
+/// from the message when it ends in a line number (see `line_from_message`), and
+/// the source URL from the one passed to the script. The line stays `None` when
+/// JavaScriptCore gives none, rather than guessing one.
+fn with_script_position(error: Error, script_url: Option<&str>) -> Error {
+    match error {
+        Error::JSException { message, source_url, line, column, stack_trace, exception } => {
+            let line = line.or_else(|| line_from_message(&message));
+            let source_url = source_url.or_else(|| script_url.map(str::to_owned));
+            
+            Error::JSException { message, source_url, line, column, stack_trace, exception }
+        },
+        error => error,
+    }
+}
+
+/// Extracts the line number from a syntax-error message that ends in
+/// " at line N" or " on line N", optionally followed by a period.
+///
+/// Only a trailing location is read, so numbers elsewhere in the message (such as
+/// user text quoted by the error) are never taken for the line.
+fn line_from_message(message: &str) -> Option<u32> {
+    let message = message.strip_suffix('.').unwrap_or(message);
+    let (prefix, digits) = message.rsplit_once(' ')?;
+    if !prefix.ends_with(" at line") && !prefix.ends_with(" on line") {
+        return None;
+    }
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|&line| line > 0)
+}
+
+/// The severity of a message logged through the console installed by
+/// `GlobalContext::install_console`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogLevel {
+    /// A message logged with `console.log`.
+    Log,
+    /// A message logged with `console.debug`.
+    Debug,
+    /// A message logged with `console.info`.
+    Info,
+    /// A message logged with `console.warn`.
+    Warn,
+    /// A message logged with `console.error`.
+    Error,
 }
 
 impl GlobalContext {
@@ -222,11 +505,26 @@ impl GlobalContext {
     /// # Returns
     ///
     /// A new GlobalContext instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if JavaScriptCore fails to create the context. Use `try_new` to handle
+    /// that case.
     pub fn new() -> Self {
-        unsafe {
-            let raw = ffi::JSGlobalContextCreate(ptr::null_mut());
-            GlobalContext { raw }
-        }
+        Self::try_new().expect("Failed to create JavaScript global context")
+    }
+    
+    /// Creates a new global JavaScript context with default settings.
+    ///
+    /// Creating a context only fails if JavaScriptCore cannot allocate it, for
+    /// example when the process is out of memory.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the new GlobalContext, or `Error::NullAccess` if
+    /// JavaScriptCore returned a null context.
+    pub fn try_new() -> Result<Self> {
+        Self::create(ptr::null_mut())
     }
     
     /// Creates a new global JavaScript context with a custom global object class.
@@ -242,11 +540,40 @@ impl GlobalContext {
     /// # Returns
     ///
     /// A new GlobalContext instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if JavaScriptCore fails to create the context. Use `try_with_class`
+    /// to handle that case.
     pub fn with_class(global_class: ffi::JSClassRef) -> Self {
-        unsafe {
-            let raw = ffi::JSGlobalContextCreate(global_class);
-            GlobalContext { raw }
+        Self::try_with_class(global_class).expect("Failed to create JavaScript global context")
+    }
+    
+    /// Creates a new global JavaScript context with a custom global object class.
+    ///
+    /// Creating a context fails if JavaScriptCore cannot allocate it or cannot
+    /// create the global object from `global_class`.
+    ///
+    /// # Arguments
+    ///
+    /// * `global_class` - The class to use for the global object.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the new GlobalContext, or `Error::NullAccess` if
+    /// JavaScriptCore returned a null context.
+    pub fn try_with_class(global_class: ffi::JSClassRef) -> Result<Self> {
+        Self::create(global_class)
+    }
+    
+    /// Creates a global context, checking the result for null.
+    fn create(global_class: ffi::JSClassRef) -> Result<Self> {
+        let raw = unsafe { ffi::JSGlobalContextCreate(global_class) };
+        if raw.is_null() {
+            return Err(Error::NullAccess("JSGlobalContextCreate returned a null context"));
         }
+        
+        Ok(GlobalContext { raw, _phantom: PhantomData })
     }
     
     /// Creates a new global JavaScript context in a specific context group.
@@ -268,15 +595,10 @@ impl GlobalContext {
                 group.unwrap_or(ptr::null()),
                 global_class.unwrap_or(ptr::null_mut()),
             );
-            GlobalContext { raw }
+            GlobalContext { raw, _phantom: PhantomData }
         }
     }
     
-    /// Returns the raw JSGlobalContextRef pointer.
-    pub(crate) fn as_raw(&self) -> ffi::JSGlobalContextRef {
-        self.raw
-    }
-    
     /// Returns a reference to the context.
     ///
     /// This provides a reference to the underlying context that can be used
@@ -296,6 +618,34 @@ impl GlobalContext {
         self.context().global_object()
     }
     
+    /// Constructs an object using a constructor from the global object.
+    ///
+    /// This is equivalent to `new Name(...args)` in JavaScript and is useful for
+    /// creating built-in objects such as `Map` or `Date` without evaluating script.
+    ///
+    /// # Arguments
+    ///
+    /// * `constructor_name` - The name of the global constructor, e.g. `"Date"`.
+    /// * `args` - The arguments to pass to the constructor.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the constructed object, or an error if the global is
+    /// not a constructor or the constructor threw.
+    pub fn construct<'a>(&'a self, constructor_name: &str, args: &[Value<'a>]) -> Result<Object<'a>> {
+        let constructor = self.global_object().get_property(constructor_name)?;
+        if !constructor.is_object() {
+            return Err(Error::InvalidType(format!("'{}' is not a constructor", constructor_name)));
+        }
+        
+        let constructor = Object::from_value(&constructor)?;
+        if !constructor.is_constructor() {
+            return Err(Error::InvalidType(format!("'{}' is not a constructor", constructor_name)));
+        }
+        
+        constructor.construct(args)
+    }
+    
     /// Evaluates JavaScript code in this context.
     ///
     /// This is a convenience method that delegates to the underlying context's
@@ -315,6 +665,73 @@ impl GlobalContext {
         self.context().evaluate_script(script, None, source_url, starting_line)
     }
     
+    /// Installs a `console` object that forwards messages to a Rust callback.
+    ///
+    /// Bare contexts have no `console`, unlike the context of a View. The installed
+    /// object has `log`, `debug`, `info`, `warn` and `error` methods, which convert
+    /// their arguments to strings, join them with spaces and pass the result to
+    /// `sink`. Format specifiers such as `%s` are not interpreted. Any existing
+    /// `console` global is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The callback that receives the level and text of each message.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating whether the console could be installed.
+    pub fn install_console<F>(&self, sink: F) -> Result<()>
+    where
+        F: Fn(LogLevel, &str) + 'static,
+    {
+        let context = self.context();
+        let sink = Rc::new(sink);
+        let console = Object::new(&context);
+        let methods = [
+            ("log", LogLevel::Log),
+            ("debug", LogLevel::Debug),
+            ("info", LogLevel::Info),
+            ("warn", LogLevel::Warn),
+            ("error", LogLevel::Error),
+        ];
+        
+        for (name, level) in methods {
+            let sink = Rc::clone(&sink);
+            let method = Value::function(&context, move |context, arguments| {
+                let message = arguments
+                    .iter()
+                    .map(|argument| match argument.to_string() {
+                        Ok(text) => text.to_string(),
+                        // Symbols can't be converted implicitly
+                        Err(_) => argument.typeof_string().to_owned(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                sink(level, &message);
+                Ok(Value::undefined(context))
+            });
+            console.set_property(name, method, PropertyAttributes::NONE)?;
+        }
+        
+        context.global_object().set_property("console", console.to_value(), PropertyAttributes::NONE)
+    }
+    
+    /// Runs pending microtasks, such as promise reactions, to completion.
+    ///
+    /// JavaScriptCore has no C API for its microtask queue. It drains the queue
+    /// itself when the outermost API call holding the VM lock returns, so every call
+    /// into this crate, including `evaluate_script`, already runs the promise
+    /// reactions it queued. This evaluates an empty script to force such a drain,
+    /// which is only useful as an explicit drain point, for example after jobs were
+    /// queued from inside a callback. Called from inside a callback it does nothing,
+    /// because the enclosing call is the one that drains.
+    ///
+    /// Exceptions thrown by jobs are not returned. Timers and other tasks that need
+    /// an event loop, such as `setTimeout`, are not run.
+    pub fn drain_microtasks(&self) {
+        let _ = self.evaluate_script("", None, 0);
+    }
+    
     /// Gets the name of this global context.
     ///
     /// The name is used for debugging purposes and is visible when inspecting the context.
@@ -375,6 +792,47 @@ impl GlobalContext {
         }
     }
     
+    /// Sets a handler for exceptions that are not caught by any script.
+    ///
+    /// Exceptions thrown synchronously are already returned as errors from
+    /// `evaluate_script`. This handler receives the ones that would otherwise be
+    /// lost: rejected promises with no rejection handler, including exceptions
+    /// thrown inside async functions and promise callbacks.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The function to call with the converted rejection reason.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error if the handler could not be installed.
+    pub fn set_uncaught_exception_handler<F>(&self, handler: F) -> Result<()>
+    where
+        F: Fn(Error) + 'static,
+    {
+        let context = self.context();
+        let function = Object::function_with_callback(&context, Some("unhandledRejection"), move |ctx, _function, _this, args| {
+            // The callback is invoked with (promise, reason)
+            let reason = match args.get(1) {
+                Some(reason) => reason.as_raw(),
+                None => Value::undefined(ctx).as_raw(),
+            };
+            handler(Error::from_js_exception(ctx.as_raw(), reason));
+            Ok(Value::undefined(ctx))
+        });
+        
+        unsafe {
+            let mut exception = ptr::null();
+            ffi::JSGlobalContextSetUnhandledRejectionCallback(self.raw, function.as_raw(), &mut exception);
+            
+            if !exception.is_null() {
+                return Err(Error::from_js_exception(self.raw, exception));
+            }
+        }
+        
+        Ok(())
+    }
+    
     /// Performs a JavaScript garbage collection cycle.
     ///
     /// This is a convenience method that delegates to the underlying context's
@@ -404,6 +862,8 @@ impl Drop for GlobalContext {
 /// same group may share and exchange JavaScript objects.
 pub struct ContextGroup {
     raw: ffi::JSContextGroupRef,
+    // Shared by clones, so the last one dropped can clear the execution time limit
+    handles: Rc<()>,
 }
 
 impl ContextGroup {
@@ -415,7 +875,7 @@ impl ContextGroup {
     pub fn new() -> Self {
         unsafe {
             let raw = ffi::JSContextGroupCreate();
-            ContextGroup { raw }
+            ContextGroup { raw, handles: Rc::new(()) }
         }
     }
     
@@ -436,6 +896,74 @@ impl ContextGroup {
     pub fn create_global_context(&self, global_class: Option<ffi::JSClassRef>) -> GlobalContext {
         GlobalContext::with_group(Some(self.raw), global_class)
     }
+    
+    /// Limits how long scripts in this group may run before they are terminated.
+    ///
+    /// When a script has run for longer than the limit, the callback is asked
+    /// whether to terminate it; returning `false` lets it run for another period of
+    /// the same length. Without a callback the script is always terminated. A
+    /// terminated script makes `evaluate_script` return
+    /// `Error::JSError("execution timed out")`.
+    ///
+    /// The limit is removed when the last `ContextGroup` handle for the group is
+    /// dropped, even if contexts in the group are still alive.
+    ///
+    /// # Arguments
+    ///
+    /// * `seconds` - The time limit in seconds.
+    /// * `callback` - An optional callback deciding whether to terminate the script.
+    pub fn set_execution_time_limit(&self, seconds: f64, callback: Option<Box<dyn Fn() -> bool>>) {
+        let limit = Box::new(ExecutionTimeLimit {
+            callback,
+            timed_out: Cell::new(false),
+        });
+        let limit_ptr = &*limit as *const ExecutionTimeLimit as *mut c_void;
+        
+        unsafe {
+            ffi::JSContextGroupSetExecutionTimeLimit(self.raw, seconds, Some(should_terminate_callback), limit_ptr);
+        }
+        EXECUTION_TIME_LIMITS.with(|limits| {
+            limits.borrow_mut().insert(self.raw as usize, limit);
+        });
+    }
+    
+    /// Removes the execution time limit set with `set_execution_time_limit`.
+    pub fn clear_execution_time_limit(&self) {
+        unsafe {
+            ffi::JSContextGroupClearExecutionTimeLimit(self.raw);
+        }
+        EXECUTION_TIME_LIMITS.with(|limits| {
+            limits.borrow_mut().remove(&(self.raw as usize));
+        });
+    }
+}
+
+/// The state of an execution time limit set on a context group.
+struct ExecutionTimeLimit {
+    callback: Option<Box<dyn Fn() -> bool>>,
+    timed_out: Cell<bool>,
+}
+
+thread_local! {
+    static EXECUTION_TIME_LIMITS: RefCell<HashMap<usize, Box<ExecutionTimeLimit>>> = RefCell::new(HashMap::new());
+}
+
+unsafe extern "C" fn should_terminate_callback(_ctx: ffi::JSContextRef, context: *mut c_void) -> bool {
+    let limit = unsafe { &*(context as *const ExecutionTimeLimit) };
+    let terminate = limit.callback.as_ref().is_none_or(|callback| callback());
+    if terminate {
+        limit.timed_out.set(true);
+    }
+    terminate
+}
+
+/// Checks whether the last script run in a context group was terminated by its time limit.
+///
+/// This resets the flag, so each termination is reported once.
+fn take_timed_out(group: ffi::JSContextGroupRef) -> bool {
+    EXECUTION_TIME_LIMITS.with(|limits| {
+        limits.borrow().get(&(group as usize)).is_some_and(|limit| limit.timed_out.replace(false))
+    })
 }
 
 impl Default for ContextGroup {
@@ -446,6 +974,9 @@ impl Default for ContextGroup {
 
 impl Drop for ContextGroup {
     fn drop(&mut self) {
+        if Rc::strong_count(&self.handles) == 1 {
+            self.clear_execution_time_limit();
+        }
         unsafe {
             ffi::JSContextGroupRelease(self.raw);
         }
@@ -456,7 +987,47 @@ impl Clone for ContextGroup {
     fn clone(&self) -> Self {
         unsafe {
             let raw = ffi::JSContextGroupRetain(self.raw);
-            ContextGroup { raw }
+            ContextGroup { raw, handles: Rc::clone(&self.handles) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn line_from_message_reads_a_trailing_line_number() {
+        assert_eq!(line_from_message("Unexpected token '}' at line 3"), Some(3));
+        assert_eq!(line_from_message("Unterminated string literal on line 12."), Some(12));
+    }
+    
+    #[test]
+    fn line_from_message_ignores_other_numbers() {
+        assert_eq!(line_from_message("Unexpected identifier 'pipeline 3'"), None);
+        assert_eq!(line_from_message("pipeline 3"), None);
+        assert_eq!(line_from_message("failed at line 3 of the input"), None);
+        assert_eq!(line_from_message("Unexpected token at line +3"), None);
+        assert_eq!(line_from_message("Unexpected token at line 0"), None);
+        assert_eq!(line_from_message("Unexpected end of script"), None);
+    }
+    
+    #[test]
+    fn with_script_position_does_not_invent_a_line() {
+        let error = Error::JSException {
+            message: "Unexpected end of script".to_owned(),
+            source_url: None,
+            line: None,
+            column: None,
+            stack_trace: None,
+            exception: None,
+        };
+        match with_script_position(error, Some("editor.js")) {
+            Error::JSException { source_url, line, .. } => {
+                assert_eq!(source_url.as_deref(), Some("editor.js"));
+                assert_eq!(line, None);
+            },
+            error => panic!("unexpected error: {error}"),
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,123 @@
+//! JavaScript dates.
+//!
+//! This module provides a wrapper around JavaScript Date objects with conversions
+//! to and from `std::time::SystemTime`. JavaScript dates have millisecond
+//! precision, so sub-millisecond components of a SystemTime are truncated.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::javascript_core::context::Context;
+use crate::javascript_core::exception::{Error, Result};
+use crate::javascript_core::object::Object;
+use crate::javascript_core::value::Value;
+
+/// A JavaScript Date.
+///
+/// The Date struct wraps a JavaScript Date object and converts between its
+/// millisecond timestamp and Rust's SystemTime.
+pub struct Date<'a> {
+    /// The underlying JavaScript object.
+    object: Object<'a>,
+}
+
+impl<'a> Date<'a> {
+    /// Creates a Date from a SystemTime.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context in which to create the date.
+    /// * `time` - The time to represent. Times before 1970 are supported.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the new Date or an error.
+    pub fn from_system_time(context: &Context<'a>, time: SystemTime) -> Result<Self> {
+        let timestamp = match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => after.as_millis() as f64,
+            Err(before) => -(before.duration().as_millis() as f64),
+        };
+        
+        let object = Object::date(context, timestamp)?;
+        Ok(Date { object })
+    }
+    
+    /// Creates a Date representing the current time.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context in which to create the date.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the new Date or an error.
+    pub fn now(context: &Context<'a>) -> Result<Self> {
+        Self::from_system_time(context, SystemTime::now())
+    }
+    
+    /// Creates a Date from an existing JavaScript object.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The JavaScript object to wrap.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the Date, or an error if the object is not a Date.
+    pub fn from_object(object: Object<'a>) -> Result<Self> {
+        if !object.to_value().is_date() {
+            return Err(Error::InvalidType("Object is not a Date".to_string()));
+        }
+        
+        Ok(Date { object })
+    }
+    
+    /// Gets the number of milliseconds since the Unix epoch.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the timestamp, which is NaN for an invalid date.
+    pub fn timestamp(&self) -> Result<f64> {
+        self.object.invoke("getTime", &[])?.to_number()
+    }
+    
+    /// Converts this date to a SystemTime.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the SystemTime, or an error if the date is invalid or
+    /// cannot be represented as a SystemTime on this platform.
+    pub fn to_system_time(&self) -> Result<SystemTime> {
+        let timestamp = self.timestamp()?;
+        
+        if !timestamp.is_finite() {
+            return Err(Error::ConversionError("Date is invalid".to_string()));
+        }
+        
+        let offset = Duration::from_millis(timestamp.abs() as u64);
+        let time = if timestamp >= 0.0 {
+            UNIX_EPOCH.checked_add(offset)
+        } else {
+            UNIX_EPOCH.checked_sub(offset)
+        };
+        
+        time.ok_or_else(|| Error::ConversionError("Date is out of range for SystemTime".to_string()))
+    }
+    
+    /// Gets the underlying JavaScript object.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the underlying JavaScript object.
+    pub fn as_object(&self) -> &Object<'a> {
+        &self.object
+    }
+    
+    /// Converts this date to a JavaScript value.
+    ///
+    /// # Returns
+    ///
+    /// A JavaScript value representing this date.
+    pub fn to_value(&self) -> Value<'a> {
+        self.object.to_value()
+    }
+}
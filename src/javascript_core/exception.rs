@@ -5,13 +5,16 @@
 //! and methods for converting JavaScriptCore exceptions into Rust errors, ensuring
 //! proper propagation of error information throughout the binding interface.
 
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::error::Error as StdError;
 use std::fmt;
-use std::ptr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, ThreadId};
 
 use crate::javascript_core::ffi;
 use crate::javascript_core::context::Context;
-use crate::javascript_core::string::String;
+use crate::javascript_core::object::Object;
 use crate::javascript_core::value::Value;
 
 /// Result type alias for operations that may produce a JavaScript exception.
@@ -20,6 +23,97 @@ use crate::javascript_core::value::Value;
 /// for functions that may result in JavaScript exceptions.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// A JavaScript exception value kept alive beyond the call that threw it.
+///
+/// The exception is protected from garbage collection, and its global context is
+/// retained, in a registry owned by the thread that threw it. This handle only
+/// holds an id into that registry, so `Error` stays `Send` and `Sync`. Dropping
+/// the handle on the thread that threw the exception releases it; dropping it on
+/// another thread leaves the exception alive until the throwing thread exits.
+pub struct ProtectedException {
+    id: u64,
+    thread: ThreadId,
+}
+
+/// An exception value protected from garbage collection, released on drop.
+struct RetainedException {
+    context: ffi::JSGlobalContextRef,
+    raw: ffi::JSValueRef,
+}
+
+impl Drop for RetainedException {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::JSValueUnprotect(self.context, self.raw);
+            ffi::JSGlobalContextRelease(self.context);
+        }
+    }
+}
+
+static NEXT_EXCEPTION_ID: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static RETAINED_EXCEPTIONS: RefCell<HashMap<u64, RetainedException>> = RefCell::new(HashMap::new());
+}
+
+impl ProtectedException {
+    /// Protects an exception value and retains its global context.
+    ///
+    /// # Safety
+    ///
+    /// Both `ctx` and `exception` must be valid pointers.
+    unsafe fn new(ctx: ffi::JSContextRef, exception: ffi::JSValueRef) -> Self {
+        let context = unsafe { ffi::JSGlobalContextRetain(ffi::JSContextGetGlobalContext(ctx)) };
+        unsafe { ffi::JSValueProtect(context, exception) };
+        
+        let id = NEXT_EXCEPTION_ID.fetch_add(1, Ordering::Relaxed);
+        RETAINED_EXCEPTIONS.with(|exceptions| {
+            exceptions.borrow_mut().insert(id, RetainedException { context, raw: exception });
+        });
+        
+        ProtectedException {
+            id,
+            thread: thread::current().id(),
+        }
+    }
+    
+    /// Returns the raw exception value, or None when called from a thread other
+    /// than the one that threw it.
+    fn raw(&self) -> Option<ffi::JSValueRef> {
+        if thread::current().id() != self.thread {
+            return None;
+        }
+        
+        RETAINED_EXCEPTIONS
+            .try_with(|exceptions| exceptions.borrow().get(&self.id).map(|exception| exception.raw))
+            .ok()
+            .flatten()
+    }
+}
+
+impl Drop for ProtectedException {
+    fn drop(&mut self) {
+        if thread::current().id() != self.thread {
+            return;
+        }
+        
+        // Release the exception outside of the registry borrow
+        let retained = RETAINED_EXCEPTIONS
+            .try_with(|exceptions| exceptions.borrow_mut().remove(&self.id))
+            .ok()
+            .flatten();
+        drop(retained);
+    }
+}
+
+impl fmt::Debug for ProtectedException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProtectedException")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
 /// Comprehensive error type for JavaScriptCore operations.
 ///
 /// This enum encompasses all potential error conditions that may arise during
@@ -39,6 +133,8 @@ pub enum Error {
         column: Option<u32>,
         /// The stack trace for the exception, if available.
         stack_trace: Option<String>,
+        /// The original exception value, protected from garbage collection.
+        exception: Option<ProtectedException>,
     },
     
     /// A general JavaScript error that doesn't have specific exception information.
@@ -61,6 +157,42 @@ pub enum Error {
 }
 
 impl Error {
+    /// Checks whether this error is a JavaScript exception.
+    ///
+    /// # Returns
+    ///
+    /// `true` for `Error::JSException`, and `false` for binding-level errors.
+    pub fn is_js_exception(&self) -> bool {
+        matches!(self, Error::JSException { .. })
+    }
+    
+    /// Returns the human-readable message of this error, without its variant prefix
+    /// or location.
+    ///
+    /// # Returns
+    ///
+    /// The error message.
+    pub fn message(&self) -> &str {
+        match self {
+            Error::JSException { message, .. } => message,
+            Error::JSError(message) | Error::InvalidType(message) | Error::ConversionError(message) => message,
+            Error::InvalidParameter(message) | Error::NullAccess(message) | Error::UnsupportedOperation(message) => message,
+        }
+    }
+    
+    /// Returns the stack trace of a JavaScript exception.
+    ///
+    /// # Returns
+    ///
+    /// The stack trace, or `None` if this is not a JavaScript exception or no
+    /// stack trace was available.
+    pub fn stack(&self) -> Option<&str> {
+        match self {
+            Error::JSException { stack_trace, .. } => stack_trace.as_deref(),
+            _ => None,
+        }
+    }
+    
     /// Creates an Error from a JavaScript exception value.
     ///
     /// This method extracts information from a JavaScript exception value to
@@ -84,8 +216,8 @@ impl Error {
             
             // Try to get the exception message
             let message = match exception_value.to_string() {
-                Ok(msg) => msg,
-                Err(_) => String::new("Unknown JavaScript exception"),
+                Ok(msg) => msg.to_string(),
+                Err(_) => "Unknown JavaScript exception".to_string(),
             };
             
             // Try to extract more information from the exception object
@@ -94,36 +226,23 @@ impl Error {
             let mut column = None;
             let mut stack_trace = None;
             
-            if exception_value.is_object() {
-                if let Ok(exception_obj) = exception_value.to_object() {
-                    // Try to get source URL
-                    if let Ok(url_value) = exception_obj.get_property("sourceURL") {
-                        if let Ok(url) = url_value.to_string() {
-                            source_url = Some(url);
-                        }
-                    }
-                    
-                    // Try to get line number
-                    if let Ok(line_value) = exception_obj.get_property("line") {
-                        if let Ok(line_num) = line_value.to_number() {
-                            line = Some(line_num as u32);
-                        }
-                    }
-                    
-                    // Try to get column number
-                    if let Ok(column_value) = exception_obj.get_property("column") {
-                        if let Ok(column_num) = column_value.to_number() {
-                            column = Some(column_num as u32);
-                        }
-                    }
-                    
-                    // Try to get stack trace
-                    if let Ok(stack_value) = exception_obj.get_property("stack") {
-                        if let Ok(stack) = stack_value.to_string() {
-                            stack_trace = Some(stack);
-                        }
-                    }
-                }
+            if exception_value.is_object()
+                && let Ok(exception_obj) = exception_value.to_object()
+            {
+                // JavaScriptCore uses different property names depending on the error type
+                source_url = string_property(&exception_obj, &["sourceURL", "fileName"]);
+                line = number_property(&exception_obj, &["line", "lineNumber", "sourceLine"]);
+                column = number_property(&exception_obj, &["column", "columnNumber"]);
+                stack_trace = string_property(&exception_obj, &["stack"]);
+            }
+            
+            // Fall back to the location of the first stack frame
+            if line.is_none()
+                && let Some((frame_url, frame_line, frame_column)) = stack_trace.as_deref().and_then(location_from_stack)
+            {
+                source_url = source_url.or(Some(frame_url));
+                line = Some(frame_line);
+                column = column.or(Some(frame_column));
             }
             
             Error::JSException {
@@ -132,10 +251,35 @@ impl Error {
                 line,
                 column,
                 stack_trace,
+                exception: Some(ProtectedException::new(ctx, exception)),
             }
         }
     }
     
+    /// Returns the original JavaScript exception value, if this error carries one.
+    ///
+    /// This allows inspecting custom properties set on the thrown object. The
+    /// returned Value is only valid while this Error is alive, since dropping the
+    /// Error unprotects the exception. The value is only available on the thread
+    /// that threw the exception.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - A context in the same context group as the one that threw the exception.
+    ///
+    /// # Returns
+    ///
+    /// The exception value, or None if this error is not a JavaScript exception
+    /// or was moved to another thread.
+    pub fn as_js_value<'a>(&self, context: &Context<'a>) -> Option<Value<'a>> {
+        match self {
+            Error::JSException { exception: Some(exception), .. } => {
+                exception.raw().map(|raw| Value::from_raw(context, raw))
+            },
+            _ => None,
+        }
+    }
+    
     /// Creates a Value representation of this error.
     ///
     /// This method converts the Error into a JavaScript Error object that can
@@ -149,6 +293,11 @@ impl Error {
     ///
     /// A JavaScript Error object representing this error.
     pub(crate) fn to_js_error<'a>(&self, context: &Context<'a>) -> Value<'a> {
+        // Rethrow the original exception value when it is still available
+        if let Some(value) = self.as_js_value(context) {
+            return value;
+        }
+        
         match self {
             Error::JSException { message, .. } => {
                 // Create a new Error object with the message
@@ -157,12 +306,12 @@ impl Error {
                     .and_then(|v| v.to_object().ok());
                 
                 if let Some(constructor) = error_constructor {
-                    let args = [Value::string(context, &message)];
-                    constructor.call_as_constructor(&args)
+                    let args = [Value::string(context, message)];
+                    constructor.construct(&args)
                         .map(|obj| obj.to_value())
-                        .unwrap_or_else(|_| Value::string(context, &message))
+                        .unwrap_or_else(|_| Value::string(context, message))
                 } else {
-                    Value::string(context, &message)
+                    Value::string(context, message)
                 }
             },
             Error::JSError(message) => {
@@ -173,7 +322,7 @@ impl Error {
                 
                 if let Some(constructor) = error_constructor {
                     let args = [Value::string(context, message)];
-                    constructor.call_as_constructor(&args)
+                    constructor.construct(&args)
                         .map(|obj| obj.to_value())
                         .unwrap_or_else(|_| Value::string(context, message))
                 } else {
@@ -189,10 +338,43 @@ impl Error {
     }
 }
 
+/// Returns the first of the named properties that holds a string.
+fn string_property(object: &Object, names: &[&str]) -> Option<String> {
+    names.iter()
+        .filter_map(|name| object.get_property(name).ok())
+        .find(|value| value.is_string())
+        .and_then(|value| value.to_string().ok())
+        .map(|string| string.to_string())
+}
+
+/// Returns the first of the named properties that holds a non-negative number.
+fn number_property(object: &Object, names: &[&str]) -> Option<u32> {
+    names.iter()
+        .filter_map(|name| object.get_property(name).ok())
+        .filter(|value| value.is_number())
+        .filter_map(|value| value.to_number().ok())
+        .find(|number| number.is_finite() && *number >= 0.0)
+        .map(|number| number as u32)
+}
+
+/// Parses the source URL, line and column of the first frame of a stack trace.
+///
+/// JavaScriptCore formats frames as `function@url:line:column`, or `url:line:column`
+/// for global code; frames in the `at url:line:column` form are accepted as well.
+pub(crate) fn location_from_stack(stack: &str) -> Option<(std::string::String, u32, u32)> {
+    let frame = stack.lines().next()?.trim();
+    let frame = frame.strip_prefix("at ").unwrap_or(frame);
+    let location = frame.rsplit_once('@').map_or(frame, |(_, location)| location);
+    
+    let (rest, column) = location.rsplit_once(':')?;
+    let (url, line) = rest.rsplit_once(':')?;
+    Some((url.to_owned(), line.parse().ok()?, column.parse().ok()?))
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Error::JSException { message, source_url, line, column, stack_trace } => {
+            Error::JSException { message, source_url, line, column, stack_trace, .. } => {
                 write!(f, "JavaScript exception: {}", message)?;
                 
                 if let Some(url) = source_url {
@@ -223,4 +405,54 @@ impl fmt::Display for Error {
     }
 }
 
-impl StdError for Error {}
\ No newline at end of file
+impl StdError for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn error_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+        assert_send_sync::<Error>();
+    }
+    
+    #[test]
+    fn location_from_stack_reads_first_frame() {
+        let stack = "fail@https://example.com/app.js:12:5\nglobal code@https://example.com/app.js:20:1";
+        assert_eq!(
+            location_from_stack(stack),
+            Some(("https://example.com/app.js".to_owned(), 12, 5))
+        );
+        assert_eq!(location_from_stack("at file:///index.html:3:9"), Some(("file:///index.html".to_owned(), 3, 9)));
+        assert_eq!(location_from_stack("no location"), None);
+    }
+    
+    #[test]
+    fn js_exception_message_and_stack_are_borrowed_without_their_location() {
+        let error = Error::JSException {
+            message: "TypeError: x is not a function".to_owned(),
+            source_url: Some("app.js".to_owned()),
+            line: Some(3),
+            column: Some(7),
+            stack_trace: Some("global code@app.js:3:7".to_owned()),
+            exception: None,
+        };
+        assert!(error.is_js_exception());
+        assert_eq!(error.message(), "TypeError: x is not a function");
+        assert_eq!(error.stack(), Some("global code@app.js:3:7"));
+        assert_eq!(
+            error.to_string(),
+            "JavaScript exception: TypeError: x is not a function at app.js:3:7\nStack trace:\nglobal code@app.js:3:7"
+        );
+    }
+    
+    #[test]
+    fn invalid_type_message_has_no_prefix_or_stack() {
+        let error = Error::InvalidType("Expected a number".to_owned());
+        assert!(!error.is_js_exception());
+        assert_eq!(error.message(), "Expected a number");
+        assert_eq!(error.stack(), None);
+        assert_eq!(error.to_string(), "Invalid type: Expected a number");
+    }
+}
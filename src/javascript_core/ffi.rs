@@ -7,10 +7,10 @@
 
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
 #![allow(dead_code)]
 
-use std::os::raw::{c_char, c_double, c_int, c_uint, c_void, c_ulong, c_uchar, c_ushort};
-use std::ptr;
+use std::os::raw::{c_char, c_double, c_int, c_uint, c_void, c_ushort};
 
 // Opaque types
 pub enum OpaqueJSContextGroup {}
@@ -46,6 +46,7 @@ pub type JSObjectCallAsFunctionCallback = Option<unsafe extern "C" fn(ctx: JSCon
 pub type JSObjectCallAsConstructorCallback = Option<unsafe extern "C" fn(ctx: JSContextRef, constructor: JSObjectRef, argumentCount: usize, arguments: *const JSValueRef, exception: *mut JSValueRef) -> JSObjectRef>;
 pub type JSObjectHasInstanceCallback = Option<unsafe extern "C" fn(ctx: JSContextRef, constructor: JSObjectRef, possibleInstance: JSValueRef, exception: *mut JSValueRef) -> bool>;
 pub type JSObjectConvertToTypeCallback = Option<unsafe extern "C" fn(ctx: JSContextRef, object: JSObjectRef, type_: JSType, exception: *mut JSValueRef) -> JSValueRef>;
+pub type JSShouldTerminateCallback = Option<unsafe extern "C" fn(ctx: JSContextRef, context: *mut c_void) -> bool>;
 
 // Enum definitions
 #[repr(C)]
@@ -149,16 +150,18 @@ pub struct JSClassDefinitionEmpty {
     pub convertToType: *const c_void,
 }
 
-extern "C" {
+unsafe extern "C" {
     pub static kJSClassDefinitionEmpty: JSClassDefinitionEmpty;
 }
 
 // Function declarations for Context API
-extern "C" {
+unsafe extern "C" {
     // Context Group Functions
     pub fn JSContextGroupCreate() -> JSContextGroupRef;
     pub fn JSContextGroupRetain(group: JSContextGroupRef) -> JSContextGroupRef;
     pub fn JSContextGroupRelease(group: JSContextGroupRef);
+    pub fn JSContextGroupSetExecutionTimeLimit(group: JSContextGroupRef, limit: c_double, callback: JSShouldTerminateCallback, context: *mut c_void);
+    pub fn JSContextGroupClearExecutionTimeLimit(group: JSContextGroupRef);
 
     // Context Functions
     pub fn JSGlobalContextCreate(globalObjectClass: JSClassRef) -> JSGlobalContextRef;
@@ -172,10 +175,11 @@ extern "C" {
     pub fn JSGlobalContextSetName(ctx: JSGlobalContextRef, name: JSStringRef);
     pub fn JSGlobalContextIsInspectable(ctx: JSGlobalContextRef) -> bool;
     pub fn JSGlobalContextSetInspectable(ctx: JSGlobalContextRef, inspectable: bool);
+    pub fn JSGlobalContextSetUnhandledRejectionCallback(ctx: JSGlobalContextRef, function: JSObjectRef, exception: *mut JSValueRef);
 }
 
 // Function declarations for String API
-extern "C" {
+unsafe extern "C" {
     pub fn JSStringCreateWithCharacters(chars: *const JSChar, numChars: usize) -> JSStringRef;
     pub fn JSStringCreateWithUTF8CString(string: *const c_char) -> JSStringRef;
     pub fn JSStringRetain(string: JSStringRef) -> JSStringRef;
@@ -189,7 +193,7 @@ extern "C" {
 }
 
 // Function declarations for Object API
-extern "C" {
+unsafe extern "C" {
     pub fn JSClassCreate(definition: *const JSClassDefinition) -> JSClassRef;
     pub fn JSClassRetain(jsClass: JSClassRef) -> JSClassRef;
     pub fn JSClassRelease(jsClass: JSClassRef);
@@ -236,7 +240,7 @@ extern "C" {
 }
 
 // Function declarations for Value API
-extern "C" {
+unsafe extern "C" {
     pub fn JSValueGetType(ctx: JSContextRef, value: JSValueRef) -> JSType;
     pub fn JSValueIsUndefined(ctx: JSContextRef, value: JSValueRef) -> bool;
     pub fn JSValueIsNull(ctx: JSContextRef, value: JSValueRef) -> bool;
@@ -269,7 +273,7 @@ extern "C" {
 }
 
 // Function declarations for Typed Array API
-extern "C" {
+unsafe extern "C" {
     pub fn JSObjectMakeTypedArray(ctx: JSContextRef, arrayType: JSTypedArrayType, length: usize, exception: *mut JSValueRef) -> JSObjectRef;
     pub fn JSObjectMakeTypedArrayWithBytesNoCopy(ctx: JSContextRef, arrayType: JSTypedArrayType, bytes: *mut c_void, byteLength: usize, bytesDeallocator: JSTypedArrayBytesDeallocator, deallocatorContext: *mut c_void, exception: *mut JSValueRef) -> JSObjectRef;
     pub fn JSObjectMakeTypedArrayWithArrayBuffer(ctx: JSContextRef, arrayType: JSTypedArrayType, buffer: JSObjectRef, exception: *mut JSValueRef) -> JSObjectRef;
@@ -285,7 +289,7 @@ extern "C" {
 }
 
 // Function declarations for Script Evaluation
-extern "C" {
+unsafe extern "C" {
     pub fn JSEvaluateScript(ctx: JSContextRef, script: JSStringRef, thisObject: JSObjectRef, sourceURL: JSStringRef, startingLineNumber: c_int, exception: *mut JSValueRef) -> JSValueRef;
     pub fn JSCheckScriptSyntax(ctx: JSContextRef, script: JSStringRef, sourceURL: JSStringRef, startingLineNumber: c_int, exception: *mut JSValueRef) -> bool;
     pub fn JSGarbageCollect(ctx: JSContextRef);
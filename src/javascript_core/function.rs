@@ -0,0 +1,228 @@
+//! Typed JavaScript functions backed by Rust closures.
+//!
+//! This module builds on `Object::function_with_callback`, generating the argument
+//! extraction and return value conversion from the closure's signature so that Rust
+//! functions can be exposed to JavaScript without manually indexing and converting
+//! the argument list.
+
+use crate::javascript_core::context::Context;
+use crate::javascript_core::exception::{Error, Result};
+use crate::javascript_core::object::Object;
+use crate::javascript_core::value::Value;
+
+/// A Rust type that can be converted into a JavaScript value.
+///
+/// This is used to convert the return value of a TypedFunction closure.
+pub trait IntoValue {
+    /// Converts this value into a JavaScript value in the given context.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context in which to create the value.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the converted JavaScript value, or an error if it could
+    /// not be created.
+    fn into_value<'a>(self, context: &Context<'a>) -> Result<Value<'a>>;
+}
+
+impl IntoValue for () {
+    fn into_value<'a>(self, context: &Context<'a>) -> Result<Value<'a>> {
+        Ok(Value::undefined(context))
+    }
+}
+
+impl IntoValue for f64 {
+    fn into_value<'a>(self, context: &Context<'a>) -> Result<Value<'a>> {
+        Ok(Value::number(context, self))
+    }
+}
+
+impl IntoValue for bool {
+    fn into_value<'a>(self, context: &Context<'a>) -> Result<Value<'a>> {
+        Ok(Value::boolean(context, self))
+    }
+}
+
+impl IntoValue for &str {
+    fn into_value<'a>(self, context: &Context<'a>) -> Result<Value<'a>> {
+        Ok(Value::string(context, self))
+    }
+}
+
+impl IntoValue for std::string::String {
+    fn into_value<'a>(self, context: &Context<'a>) -> Result<Value<'a>> {
+        Ok(Value::string(context, &self))
+    }
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+    fn into_value<'a>(self, context: &Context<'a>) -> Result<Value<'a>> {
+        let values = self
+            .into_iter()
+            .map(|item| item.into_value(context))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Object::array(context, &values)?.to_value())
+    }
+}
+
+impl<T: IntoValue> IntoValue for Option<T> {
+    /// Converts `None` into `null`.
+    fn into_value<'a>(self, context: &Context<'a>) -> Result<Value<'a>> {
+        match self {
+            Some(value) => value.into_value(context),
+            None => Ok(Value::null(context)),
+        }
+    }
+}
+
+/// A Rust type that can be extracted from a JavaScript value.
+///
+/// This is used to convert the arguments of a TypedFunction closure. Unlike the
+/// `TryFrom<&Value>` conversions it builds on, it is also implemented for
+/// containers such as `Vec<T>` and `Option<T>`.
+pub trait FromValue: Sized {
+    /// Extracts a Rust value from a JavaScript value without coercion.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The JavaScript value to convert.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the converted value, or `Error::InvalidType` if the value
+    /// has the wrong type.
+    fn from_value(value: &Value<'_>) -> Result<Self>;
+}
+
+impl FromValue for f64 {
+    fn from_value(value: &Value<'_>) -> Result<Self> {
+        f64::try_from(value)
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Value<'_>) -> Result<Self> {
+        bool::try_from(value)
+    }
+}
+
+impl FromValue for std::string::String {
+    fn from_value(value: &Value<'_>) -> Result<Self> {
+        std::string::String::try_from(value)
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    /// Converts each element of a JavaScript array.
+    fn from_value(value: &Value<'_>) -> Result<Self> {
+        value.array_iter()?.map(|item| T::from_value(&item?)).collect()
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    /// Converts `null` and `undefined` into `None`.
+    fn from_value(value: &Value<'_>) -> Result<Self> {
+        if value.is_null() || value.is_undefined() {
+            return Ok(None);
+        }
+        
+        T::from_value(value).map(Some)
+    }
+}
+
+/// Extracts an argument, treating a missing argument as `undefined`.
+///
+/// A missing argument is only accepted if its type accepts `undefined`, such as
+/// `Option<T>`; otherwise too few arguments were passed.
+fn argument<T: FromValue>(context: &Context<'_>, arguments: &[Value<'_>], index: usize) -> Result<T> {
+    match arguments.get(index) {
+        Some(value) => T::from_value(value),
+        None => T::from_value(&Value::undefined(context))
+            .map_err(|_| Error::InvalidParameter("Too few arguments passed to function")),
+    }
+}
+
+/// A Rust closure whose arguments can be extracted from JavaScript values.
+///
+/// This trait is implemented for closures taking up to six arguments, each of
+/// which implements FromValue, and returning a type that implements IntoValue.
+/// It is not meant to be implemented manually.
+pub trait TypedCallback<Args>: 'static {
+    /// Extracts the arguments, invokes the closure and converts the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context in which the function was called.
+    /// * `arguments` - The arguments passed from JavaScript.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the converted return value, or an error if too few
+    /// arguments were passed or an argument has the wrong type.
+    fn invoke<'c>(&self, context: &Context<'c>, arguments: &[Value<'c>]) -> Result<Value<'c>>;
+}
+
+macro_rules! impl_typed_callback {
+    ($($arg:ident : $index:tt),*) => {
+        impl<Func, Ret, $($arg,)*> TypedCallback<($($arg,)*)> for Func
+        where
+            Func: Fn($($arg),*) -> Ret + 'static,
+            Ret: IntoValue,
+            $($arg: FromValue,)*
+        {
+            #[allow(unused_variables, unused_comparisons)]
+            fn invoke<'c>(&self, context: &Context<'c>, arguments: &[Value<'c>]) -> Result<Value<'c>> {
+                self($(argument::<$arg>(context, arguments, $index)?),*).into_value(context)
+            }
+        }
+    };
+}
+
+impl_typed_callback!();
+impl_typed_callback!(A: 0);
+impl_typed_callback!(A: 0, B: 1);
+impl_typed_callback!(A: 0, B: 1, C: 2);
+impl_typed_callback!(A: 0, B: 1, C: 2, D: 3);
+impl_typed_callback!(A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_typed_callback!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+
+/// A JavaScript function created from a typed Rust closure.
+///
+/// The closure's parameter types determine how the JavaScript arguments are
+/// converted, and its return type how the result is converted back:
+///
+/// ```ignore
+/// let add = TypedFunction::new(&context, |a: f64, b: f64| -> f64 { a + b });
+/// context.set_global("add", add.to_value())?;
+/// ```
+///
+/// Calling the function with too few arguments or with an argument of the wrong
+/// type throws a JavaScript error instead of invoking the closure. Missing
+/// arguments are accepted for `Option` parameters, and extra arguments are
+/// ignored, as they would be by a JavaScript function. Use
+/// `Context::register_fn` to install such a function as a global.
+pub struct TypedFunction;
+
+impl TypedFunction {
+    /// Creates a JavaScript function that invokes a typed Rust closure.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context in which to create the function.
+    /// * `callback` - The Rust closure to invoke.
+    ///
+    /// # Returns
+    ///
+    /// The JavaScript function object.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<'a, Args, F>(context: &Context<'a>, callback: F) -> Object<'a>
+    where
+        F: TypedCallback<Args>,
+    {
+        Object::function_with_callback(context, None, move |context, _function, _this, arguments| {
+            callback.invoke(context, arguments)
+        })
+    }
+}
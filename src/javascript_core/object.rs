@@ -5,19 +5,21 @@
 //! complexity of memory management and error handling through RAII principles and type safety,
 //! while exposing the full functionality of the underlying C API.
 
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::any::Any;
 use std::convert::TryFrom;
 use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
-use std::mem;
-use std::os::raw::{c_char, c_void, c_int, c_uint};
+use std::os::raw::{c_void, c_uint};
 use std::ptr;
-use std::slice;
+use std::rc::{Rc, Weak};
 
 use crate::javascript_core::context::Context;
-use crate::javascript_core::error::{Error, Result};
+use crate::javascript_core::exception::{Error, Result};
 use crate::javascript_core::ffi;
 use crate::javascript_core::string::String;
-use crate::javascript_core::value::Value;
+use crate::javascript_core::value::{ArrayIter, Value};
 
 /// Attributes that can be assigned to JavaScript object properties.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -49,6 +51,26 @@ impl PropertyAttributes {
         if dont_delete { attrs |= ffi::kJSPropertyAttributeDontDelete; }
         PropertyAttributes(attrs)
     }
+
+    /// Returns true if all attributes in `other` are also set in `self`.
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns true if the property is read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.contains(Self::READ_ONLY)
+    }
+
+    /// Returns true if the property is excluded from for-in enumeration.
+    pub fn is_dont_enum(&self) -> bool {
+        self.contains(Self::DONT_ENUM)
+    }
+
+    /// Returns true if the property cannot be deleted.
+    pub fn is_dont_delete(&self) -> bool {
+        self.contains(Self::DONT_DELETE)
+    }
 }
 
 impl std::ops::BitOr for PropertyAttributes {
@@ -106,7 +128,7 @@ pub type FinalizeCallback = Box<dyn Fn(&Object)>;
 pub type HasPropertyCallback = Box<dyn Fn(&Context, &Object, &str) -> bool>;
 
 /// A callback to get a property value.
-pub type GetPropertyCallback = Box<dyn Fn(&Context, &Object, &str) -> Result<Value>>;
+pub type GetPropertyCallback = Box<dyn for<'c> Fn(&Context<'c>, &Object<'c>, &str) -> Result<Value<'c>>>;
 
 /// A callback to set a property value.
 pub type SetPropertyCallback = Box<dyn Fn(&Context, &Object, &str, Value) -> Result<bool>>;
@@ -118,16 +140,16 @@ pub type DeletePropertyCallback = Box<dyn Fn(&Context, &Object, &str) -> Result<
 pub type GetPropertyNamesCallback = Box<dyn Fn(&Context, &Object, &mut Vec<String>)>;
 
 /// A callback to call an object as a function.
-pub type CallAsFunctionCallback = Box<dyn Fn(&Context, &Object, Option<&Object>, &[Value]) -> Result<Value>>;
+pub type CallAsFunctionCallback = Box<dyn for<'c> Fn(&Context<'c>, &Object<'c>, Option<&Object<'c>>, &[Value<'c>]) -> Result<Value<'c>>>;
 
 /// A callback to call an object as a constructor.
-pub type CallAsConstructorCallback = Box<dyn Fn(&Context, &Object, &[Value]) -> Result<Object>>;
+pub type CallAsConstructorCallback = Box<dyn for<'c> Fn(&Context<'c>, &Object<'c>, &[Value<'c>]) -> Result<Object<'c>>>;
 
 /// A callback to determine if an object is an instance of a constructor.
 pub type HasInstanceCallback = Box<dyn Fn(&Context, &Object, &Value) -> Result<bool>>;
 
 /// A callback to convert an object to a primitive type.
-pub type ConvertToTypeCallback = Box<dyn Fn(&Context, &Object, ffi::JSType) -> Result<Value>>;
+pub type ConvertToTypeCallback = Box<dyn for<'c> Fn(&Context<'c>, &Object<'c>, ffi::JSType) -> Result<Value<'c>>>;
 
 /// Represents a static value property definition.
 pub struct StaticValue {
@@ -235,6 +257,36 @@ struct ClassCallbackData {
     callbacks: Box<ClassCallbacks>,
 }
 
+impl Drop for ClassCallbackData {
+    fn drop(&mut self) {
+        let key = self as *const ClassCallbackData as usize;
+        let _ = CLASS_CALLBACK_DATA.try_with(|data| data.borrow_mut().remove(&key));
+    }
+}
+
+thread_local! {
+    // Callback data owned by live classes, so the finalizer can tell it apart from
+    // private data supplied by the caller.
+    static CLASS_CALLBACK_DATA: RefCell<HashSet<usize>> = RefCell::new(HashSet::new());
+}
+
+// Whether `data` points at callback data shared by a live class or its objects.
+fn is_class_callback_data(data: *mut c_void) -> bool {
+    CLASS_CALLBACK_DATA
+        .try_with(|registered| registered.borrow().contains(&(data as usize)))
+        .unwrap_or(false)
+}
+
+// Get the class callback data stored as an object's private data. Private data
+// supplied by the caller is not callback data, so the class callbacks ignore it.
+unsafe fn class_callback_data<'d>(object: ffi::JSObjectRef) -> Option<&'d ClassCallbackData> {
+    let data = unsafe { ffi::JSObjectGetPrivate(object) } as *mut ClassCallbackData;
+    if data.is_null() || !is_class_callback_data(data as *mut c_void) {
+        return None;
+    }
+    Some(unsafe { &*data })
+}
+
 struct ClassCallbacks {
     initialize: Option<InitializeCallback>,
     finalize: Option<FinalizeCallback>,
@@ -254,14 +306,12 @@ struct ClassCallbacks {
 // C callback implementations
 extern "C" fn initialize_callback(ctx: ffi::JSContextRef, object: ffi::JSObjectRef) {
     unsafe {
-        let data = ffi::JSObjectGetPrivate(object) as *mut ClassCallbackData;
-        if !data.is_null() {
-            let data = &*data;
-            if let Some(ref callback) = data.callbacks.initialize {
-                let context = Context::from_raw(ctx);
-                let obj = Object::from_raw(context, object);
-                callback(&context, &obj);
-            }
+        if let Some(data) = class_callback_data(object)
+            && let Some(ref callback) = data.callbacks.initialize
+        {
+            let context = Context::from_raw(ctx);
+            let obj = Object::from_raw(context.clone(), object);
+            callback(&context, &obj);
         }
     }
 }
@@ -269,7 +319,7 @@ extern "C" fn initialize_callback(ctx: ffi::JSContextRef, object: ffi::JSObjectR
 extern "C" fn finalize_callback(object: ffi::JSObjectRef) {
     unsafe {
         let data = ffi::JSObjectGetPrivate(object) as *mut ClassCallbackData;
-        if !data.is_null() {
+        if !data.is_null() && is_class_callback_data(data as *mut c_void) {
             // Call the finalize callback if it exists
             let data_ref = &*data;
             if let Some(ref callback) = data_ref.callbacks.finalize {
@@ -279,24 +329,22 @@ extern "C" fn finalize_callback(object: ffi::JSObjectRef) {
                 callback(&obj);
             }
             
-            // Free the callback data
-            Box::from_raw(data);
+            // Release this object's reference to the callback data
+            drop(Rc::from_raw(data as *const ClassCallbackData));
         }
     }
 }
 
 extern "C" fn has_property_callback(ctx: ffi::JSContextRef, object: ffi::JSObjectRef, property_name: ffi::JSStringRef) -> bool {
     unsafe {
-        let data = ffi::JSObjectGetPrivate(object) as *mut ClassCallbackData;
-        if !data.is_null() {
-            let data = &*data;
-            if let Some(ref callback) = data.callbacks.has_property {
-                let context = Context::from_raw(ctx);
-                let obj = Object::from_raw(context, object);
-                let name = String::from_raw(property_name);
-                
-                return callback(&context, &obj, &name);
-            }
+        if let Some(data) = class_callback_data(object)
+            && let Some(ref callback) = data.callbacks.has_property
+        {
+            let context = Context::from_raw(ctx);
+            let obj = Object::from_raw(context.clone(), object);
+            let name = String::from_raw(property_name);
+            
+            return callback(&context, &obj, &name.to_string());
         }
         false
     }
@@ -304,22 +352,20 @@ extern "C" fn has_property_callback(ctx: ffi::JSContextRef, object: ffi::JSObjec
 
 extern "C" fn get_property_callback(ctx: ffi::JSContextRef, object: ffi::JSObjectRef, property_name: ffi::JSStringRef, exception: *mut ffi::JSValueRef) -> ffi::JSValueRef {
     unsafe {
-        let data = ffi::JSObjectGetPrivate(object) as *mut ClassCallbackData;
-        if !data.is_null() {
-            let data = &*data;
-            if let Some(ref callback) = data.callbacks.get_property {
-                let context = Context::from_raw(ctx);
-                let obj = Object::from_raw(context, object);
-                let name = String::from_raw(property_name);
-                
-                match callback(&context, &obj, &name) {
-                    Ok(value) => return value.as_raw(),
-                    Err(err) => {
-                        if !exception.is_null() {
-                            *exception = Value::from_error(&context, &err).as_raw();
-                        }
-                        return ptr::null();
+        if let Some(data) = class_callback_data(object)
+            && let Some(ref callback) = data.callbacks.get_property
+        {
+            let context = Context::from_raw(ctx);
+            let obj = Object::from_raw(context.clone(), object);
+            let name = String::from_raw(property_name);
+            
+            match callback(&context, &obj, &name.to_string()) {
+                Ok(value) => return value.as_raw(),
+                Err(err) => {
+                    if !exception.is_null() {
+                        *exception = Value::from_error(&context, &err).as_raw();
                     }
+                    return ptr::null();
                 }
             }
         }
@@ -329,23 +375,21 @@ extern "C" fn get_property_callback(ctx: ffi::JSContextRef, object: ffi::JSObjec
 
 extern "C" fn set_property_callback(ctx: ffi::JSContextRef, object: ffi::JSObjectRef, property_name: ffi::JSStringRef, value: ffi::JSValueRef, exception: *mut ffi::JSValueRef) -> bool {
     unsafe {
-        let data = ffi::JSObjectGetPrivate(object) as *mut ClassCallbackData;
-        if !data.is_null() {
-            let data = &*data;
-            if let Some(ref callback) = data.callbacks.set_property {
-                let context = Context::from_raw(ctx);
-                let obj = Object::from_raw(context, object);
-                let name = String::from_raw(property_name);
-                let val = Value::from_raw(context, value);
-                
-                match callback(&context, &obj, &name, val) {
-                    Ok(result) => return result,
-                    Err(err) => {
-                        if !exception.is_null() {
-                            *exception = Value::from_error(&context, &err).as_raw();
-                        }
-                        return false;
+        if let Some(data) = class_callback_data(object)
+            && let Some(ref callback) = data.callbacks.set_property
+        {
+            let context = Context::from_raw(ctx);
+            let obj = Object::from_raw(context.clone(), object);
+            let name = String::from_raw(property_name);
+            let val = Value::from_raw(&context, value);
+            
+            match callback(&context, &obj, &name.to_string(), val) {
+                Ok(result) => return result,
+                Err(err) => {
+                    if !exception.is_null() {
+                        *exception = Value::from_error(&context, &err).as_raw();
                     }
+                    return false;
                 }
             }
         }
@@ -355,22 +399,20 @@ extern "C" fn set_property_callback(ctx: ffi::JSContextRef, object: ffi::JSObjec
 
 extern "C" fn delete_property_callback(ctx: ffi::JSContextRef, object: ffi::JSObjectRef, property_name: ffi::JSStringRef, exception: *mut ffi::JSValueRef) -> bool {
     unsafe {
-        let data = ffi::JSObjectGetPrivate(object) as *mut ClassCallbackData;
-        if !data.is_null() {
-            let data = &*data;
-            if let Some(ref callback) = data.callbacks.delete_property {
-                let context = Context::from_raw(ctx);
-                let obj = Object::from_raw(context, object);
-                let name = String::from_raw(property_name);
-                
-                match callback(&context, &obj, &name) {
-                    Ok(result) => return result,
-                    Err(err) => {
-                        if !exception.is_null() {
-                            *exception = Value::from_error(&context, &err).as_raw();
-                        }
-                        return false;
+        if let Some(data) = class_callback_data(object)
+            && let Some(ref callback) = data.callbacks.delete_property
+        {
+            let context = Context::from_raw(ctx);
+            let obj = Object::from_raw(context.clone(), object);
+            let name = String::from_raw(property_name);
+            
+            match callback(&context, &obj, &name.to_string()) {
+                Ok(result) => return result,
+                Err(err) => {
+                    if !exception.is_null() {
+                        *exception = Value::from_error(&context, &err).as_raw();
                     }
+                    return false;
                 }
             }
         }
@@ -380,19 +422,17 @@ extern "C" fn delete_property_callback(ctx: ffi::JSContextRef, object: ffi::JSOb
 
 extern "C" fn get_property_names_callback(ctx: ffi::JSContextRef, object: ffi::JSObjectRef, property_names: ffi::JSPropertyNameAccumulatorRef) {
     unsafe {
-        let data = ffi::JSObjectGetPrivate(object) as *mut ClassCallbackData;
-        if !data.is_null() {
-            let data = &*data;
-            if let Some(ref callback) = data.callbacks.get_property_names {
-                let context = Context::from_raw(ctx);
-                let obj = Object::from_raw(context, object);
-                let mut names = Vec::new();
-                
-                callback(&context, &obj, &mut names);
-                
-                for name in names {
-                    ffi::JSPropertyNameAccumulatorAddName(property_names, name.as_raw());
-                }
+        if let Some(data) = class_callback_data(object)
+            && let Some(ref callback) = data.callbacks.get_property_names
+        {
+            let context = Context::from_raw(ctx);
+            let obj = Object::from_raw(context.clone(), object);
+            let mut names = Vec::new();
+            
+            callback(&context, &obj, &mut names);
+            
+            for name in names {
+                ffi::JSPropertyNameAccumulatorAddName(property_names, name.as_raw());
             }
         }
     }
@@ -400,35 +440,33 @@ extern "C" fn get_property_names_callback(ctx: ffi::JSContextRef, object: ffi::J
 
 extern "C" fn call_as_function_callback(ctx: ffi::JSContextRef, function: ffi::JSObjectRef, this_object: ffi::JSObjectRef, argument_count: usize, arguments: *const ffi::JSValueRef, exception: *mut ffi::JSValueRef) -> ffi::JSValueRef {
     unsafe {
-        let data = ffi::JSObjectGetPrivate(function) as *mut ClassCallbackData;
-        if !data.is_null() {
-            let data = &*data;
-            if let Some(ref callback) = data.callbacks.call_as_function {
-                let context = Context::from_raw(ctx);
-                let func = Object::from_raw(context, function);
-                let this = if this_object.is_null() {
-                    None
-                } else {
-                    Some(Object::from_raw(context, this_object))
-                };
-                
-                let args = if argument_count == 0 || arguments.is_null() {
-                    Vec::new()
-                } else {
-                    let args_slice = std::slice::from_raw_parts(arguments, argument_count);
-                    args_slice.iter()
-                        .map(|&arg| Value::from_raw(context, arg))
-                        .collect()
-                };
-                
-                match callback(&context, &func, this.as_ref(), &args) {
-                    Ok(result) => return result.as_raw(),
-                    Err(err) => {
-                        if !exception.is_null() {
-                            *exception = Value::from_error(&context, &err).as_raw();
-                        }
-                        return ptr::null();
+        if let Some(data) = class_callback_data(function)
+            && let Some(ref callback) = data.callbacks.call_as_function
+        {
+            let context = Context::from_raw(ctx);
+            let func = Object::from_raw(context.clone(), function);
+            let this = if this_object.is_null() {
+                None
+            } else {
+                Some(Object::from_raw(context.clone(), this_object))
+            };
+            
+            let args = if argument_count == 0 || arguments.is_null() {
+                Vec::new()
+            } else {
+                let args_slice = std::slice::from_raw_parts(arguments, argument_count);
+                args_slice.iter()
+                    .map(|&arg| Value::from_raw(&context, arg))
+                    .collect()
+            };
+            
+            match callback(&context, &func, this.as_ref(), &args) {
+                Ok(result) => return result.as_raw(),
+                Err(err) => {
+                    if !exception.is_null() {
+                        *exception = Value::from_error(&context, &err).as_raw();
                     }
+                    return ptr::null();
                 }
             }
         }
@@ -438,30 +476,28 @@ extern "C" fn call_as_function_callback(ctx: ffi::JSContextRef, function: ffi::J
 
 extern "C" fn call_as_constructor_callback(ctx: ffi::JSContextRef, constructor: ffi::JSObjectRef, argument_count: usize, arguments: *const ffi::JSValueRef, exception: *mut ffi::JSValueRef) -> ffi::JSObjectRef {
     unsafe {
-        let data = ffi::JSObjectGetPrivate(constructor) as *mut ClassCallbackData;
-        if !data.is_null() {
-            let data = &*data;
-            if let Some(ref callback) = data.callbacks.call_as_constructor {
-                let context = Context::from_raw(ctx);
-                let ctor = Object::from_raw(context, constructor);
-                
-                let args = if argument_count == 0 || arguments.is_null() {
-                    Vec::new()
-                } else {
-                    let args_slice = std::slice::from_raw_parts(arguments, argument_count);
-                    args_slice.iter()
-                        .map(|&arg| Value::from_raw(context, arg))
-                        .collect()
-                };
-                
-                match callback(&context, &ctor, &args) {
-                    Ok(result) => return result.as_raw(),
-                    Err(err) => {
-                        if !exception.is_null() {
-                            *exception = Value::from_error(&context, &err).as_raw();
-                        }
-                        return ptr::null_mut();
+        if let Some(data) = class_callback_data(constructor)
+            && let Some(ref callback) = data.callbacks.call_as_constructor
+        {
+            let context = Context::from_raw(ctx);
+            let ctor = Object::from_raw(context.clone(), constructor);
+            
+            let args = if argument_count == 0 || arguments.is_null() {
+                Vec::new()
+            } else {
+                let args_slice = std::slice::from_raw_parts(arguments, argument_count);
+                args_slice.iter()
+                    .map(|&arg| Value::from_raw(&context, arg))
+                    .collect()
+            };
+            
+            match callback(&context, &ctor, &args) {
+                Ok(result) => return result.as_raw(),
+                Err(err) => {
+                    if !exception.is_null() {
+                        *exception = Value::from_error(&context, &err).as_raw();
                     }
+                    return ptr::null_mut();
                 }
             }
         }
@@ -471,22 +507,20 @@ extern "C" fn call_as_constructor_callback(ctx: ffi::JSContextRef, constructor:
 
 extern "C" fn has_instance_callback(ctx: ffi::JSContextRef, constructor: ffi::JSObjectRef, possible_instance: ffi::JSValueRef, exception: *mut ffi::JSValueRef) -> bool {
     unsafe {
-        let data = ffi::JSObjectGetPrivate(constructor) as *mut ClassCallbackData;
-        if !data.is_null() {
-            let data = &*data;
-            if let Some(ref callback) = data.callbacks.has_instance {
-                let context = Context::from_raw(ctx);
-                let ctor = Object::from_raw(context, constructor);
-                let instance = Value::from_raw(context, possible_instance);
-                
-                match callback(&context, &ctor, &instance) {
-                    Ok(result) => return result,
-                    Err(err) => {
-                        if !exception.is_null() {
-                            *exception = Value::from_error(&context, &err).as_raw();
-                        }
-                        return false;
+        if let Some(data) = class_callback_data(constructor)
+            && let Some(ref callback) = data.callbacks.has_instance
+        {
+            let context = Context::from_raw(ctx);
+            let ctor = Object::from_raw(context.clone(), constructor);
+            let instance = Value::from_raw(&context, possible_instance);
+            
+            match callback(&context, &ctor, &instance) {
+                Ok(result) => return result,
+                Err(err) => {
+                    if !exception.is_null() {
+                        *exception = Value::from_error(&context, &err).as_raw();
                     }
+                    return false;
                 }
             }
         }
@@ -496,21 +530,19 @@ extern "C" fn has_instance_callback(ctx: ffi::JSContextRef, constructor: ffi::JS
 
 extern "C" fn convert_to_type_callback(ctx: ffi::JSContextRef, object: ffi::JSObjectRef, type_: ffi::JSType, exception: *mut ffi::JSValueRef) -> ffi::JSValueRef {
     unsafe {
-        let data = ffi::JSObjectGetPrivate(object) as *mut ClassCallbackData;
-        if !data.is_null() {
-            let data = &*data;
-            if let Some(ref callback) = data.callbacks.convert_to_type {
-                let context = Context::from_raw(ctx);
-                let obj = Object::from_raw(context, object);
-                
-                match callback(&context, &obj, type_) {
-                    Ok(result) => return result.as_raw(),
-                    Err(err) => {
-                        if !exception.is_null() {
-                            *exception = Value::from_error(&context, &err).as_raw();
-                        }
-                        return ptr::null();
+        if let Some(data) = class_callback_data(object)
+            && let Some(ref callback) = data.callbacks.convert_to_type
+        {
+            let context = Context::from_raw(ctx);
+            let obj = Object::from_raw(context.clone(), object);
+            
+            match callback(&context, &obj, type_) {
+                Ok(result) => return result.as_raw(),
+                Err(err) => {
+                    if !exception.is_null() {
+                        *exception = Value::from_error(&context, &err).as_raw();
                     }
+                    return ptr::null();
                 }
             }
         }
@@ -520,19 +552,17 @@ extern "C" fn convert_to_type_callback(ctx: ffi::JSContextRef, object: ffi::JSOb
 
 extern "C" fn static_value_getter(ctx: ffi::JSContextRef, object: ffi::JSObjectRef, property_name: ffi::JSStringRef, exception: *mut ffi::JSValueRef) -> ffi::JSValueRef {
     unsafe {
-        let data = ffi::JSObjectGetPrivate(object) as *mut ClassCallbackData;
-        if !data.is_null() {
-            let data = &*data;
+        if let Some(data) = class_callback_data(object) {
             let name = String::from_raw(property_name);
             
             for (stored_name, getter, _) in &data.callbacks.static_values {
                 let stored_name_str = String::from_utf8_buffer(CStr::from_ptr(stored_name.as_ptr()).to_bytes());
                 if name == stored_name_str {
-                    if let Some(ref getter_fn) = getter {
+                    if let Some(getter_fn) = getter {
                         let context = Context::from_raw(ctx);
-                        let obj = Object::from_raw(context, object);
+                        let obj = Object::from_raw(context.clone(), object);
                         
-                        match getter_fn(&context, &obj, &name) {
+                        match getter_fn(&context, &obj, &name.to_string()) {
                             Ok(value) => return value.as_raw(),
                             Err(err) => {
                                 if !exception.is_null() {
@@ -552,20 +582,18 @@ extern "C" fn static_value_getter(ctx: ffi::JSContextRef, object: ffi::JSObjectR
 
 extern "C" fn static_value_setter(ctx: ffi::JSContextRef, object: ffi::JSObjectRef, property_name: ffi::JSStringRef, value: ffi::JSValueRef, exception: *mut ffi::JSValueRef) -> bool {
     unsafe {
-        let data = ffi::JSObjectGetPrivate(object) as *mut ClassCallbackData;
-        if !data.is_null() {
-            let data = &*data;
+        if let Some(data) = class_callback_data(object) {
             let name = String::from_raw(property_name);
             
             for (stored_name, _, setter) in &data.callbacks.static_values {
                 let stored_name_str = String::from_utf8_buffer(CStr::from_ptr(stored_name.as_ptr()).to_bytes());
                 if name == stored_name_str {
-                    if let Some(ref setter_fn) = setter {
+                    if let Some(setter_fn) = setter {
                         let context = Context::from_raw(ctx);
-                        let obj = Object::from_raw(context, object);
-                        let val = Value::from_raw(context, value);
+                        let obj = Object::from_raw(context.clone(), object);
+                        let val = Value::from_raw(&context, value);
                         
-                        match setter_fn(&context, &obj, &name, val) {
+                        match setter_fn(&context, &obj, &name.to_string(), val) {
                             Ok(result) => return result,
                             Err(err) => {
                                 if !exception.is_null() {
@@ -598,24 +626,22 @@ extern "C" fn static_function_callback(ctx: ffi::JSContextRef, function: ffi::JS
                 let name = String::from_raw(name_str);
                 
                 // Get the class data from the this object
-                let data = ffi::JSObjectGetPrivate(this_object) as *mut ClassCallbackData;
-                if !data.is_null() {
-                    let data = &*data;
+                if let Some(data) = class_callback_data(this_object) {
                     
                     // Find the function by name
                     for (stored_name, callback) in &data.callbacks.static_functions {
                         let stored_name_str = String::from_utf8_buffer(CStr::from_ptr(stored_name.as_ptr()).to_bytes());
                         if name == stored_name_str {
                             let context = Context::from_raw(ctx);
-                            let func = Object::from_raw(context, function);
-                            let this = Object::from_raw(context, this_object);
+                            let func = Object::from_raw(context.clone(), function);
+                            let this = Object::from_raw(context.clone(), this_object);
                             
                             let args = if argument_count == 0 || arguments.is_null() {
                                 Vec::new()
                             } else {
                                 let args_slice = std::slice::from_raw_parts(arguments, argument_count);
                                 args_slice.iter()
-                                    .map(|&arg| Value::from_raw(context, arg))
+                                    .map(|&arg| Value::from_raw(&context, arg))
                                     .collect()
                             };
                             
@@ -639,9 +665,102 @@ extern "C" fn static_function_callback(ctx: ffi::JSContextRef, function: ffi::JS
     }
 }
 
+thread_local! {
+    // Constructors made by JSObjectMakeConstructor can't hold private data, so their
+    // callbacks are looked up by constructor object instead. Each callback is owned
+    // by a keeper object stored on its constructor, so it lives as long as the
+    // constructor does.
+    static CONSTRUCTOR_CALLBACKS: RefCell<HashMap<usize, Weak<CallAsConstructorCallback>>> = RefCell::new(HashMap::new());
+    
+    // The class of constructor keepers, created on first use.
+    static CONSTRUCTOR_KEEPER_CLASS: ffi::JSClassRef = unsafe {
+        let definition = ffi::JSClassDefinition {
+            version: 0,
+            attributes: 0,
+            className: c"ConstructorCallback".as_ptr(),
+            parentClass: ptr::null_mut(),
+            staticValues: ptr::null(),
+            staticFunctions: ptr::null(),
+            initialize: None,
+            finalize: Some(constructor_keeper_finalize),
+            hasProperty: None,
+            getProperty: None,
+            setProperty: None,
+            deleteProperty: None,
+            getPropertyNames: None,
+            callAsFunction: None,
+            callAsConstructor: None,
+            hasInstance: None,
+            convertToType: None,
+        };
+        ffi::JSClassCreate(&definition)
+    };
+}
+
+// The private data of a constructor keeper.
+struct ConstructorKeeper {
+    constructor: usize,
+    callback: Rc<CallAsConstructorCallback>,
+}
+
+extern "C" fn constructor_keeper_finalize(object: ffi::JSObjectRef) {
+    unsafe {
+        let keeper = ffi::JSObjectGetPrivate(object) as *mut ConstructorKeeper;
+        if keeper.is_null() {
+            return;
+        }
+        let keeper = Box::from_raw(keeper);
+        
+        // Objects are finalized lazily, so a newer constructor may already use the
+        // same address; only remove the entry if it is still this keeper's
+        let _ = CONSTRUCTOR_CALLBACKS.try_with(|callbacks| {
+            let mut callbacks = callbacks.borrow_mut();
+            if let Some(callback) = callbacks.get(&keeper.constructor)
+                && ptr::addr_eq(callback.as_ptr(), Rc::as_ptr(&keeper.callback))
+            {
+                callbacks.remove(&keeper.constructor);
+            }
+        });
+    }
+}
+
+extern "C" fn class_constructor_callback(ctx: ffi::JSContextRef, constructor: ffi::JSObjectRef, argument_count: usize, arguments: *const ffi::JSValueRef, exception: *mut ffi::JSValueRef) -> ffi::JSObjectRef {
+    unsafe {
+        let callback = CONSTRUCTOR_CALLBACKS.with(|callbacks| callbacks.borrow().get(&(constructor as usize)).and_then(Weak::upgrade));
+        if let Some(callback) = callback {
+            let context = Context::from_raw(ctx);
+            let ctor = Object::from_raw(context.clone(), constructor);
+            
+            let args = if argument_count == 0 || arguments.is_null() {
+                Vec::new()
+            } else {
+                let args_slice = std::slice::from_raw_parts(arguments, argument_count);
+                args_slice.iter()
+                    .map(|&arg| Value::from_raw(&context, arg))
+                    .collect()
+            };
+            
+            match callback(&context, &ctor, &args) {
+                Ok(result) => return result.as_raw(),
+                Err(err) => {
+                    if !exception.is_null() {
+                        *exception = Value::from_error(&context, &err).as_raw();
+                    }
+                    return ptr::null_mut();
+                }
+            }
+        }
+        ptr::null_mut()
+    }
+}
+
 /// A JavaScript class.
+///
+/// The class callbacks are shared between every copy of the class and every object
+/// created from it, and are freed once the last of them is gone.
 pub struct Class {
     raw: ffi::JSClassRef,
+    data: Option<Rc<ClassCallbackData>>,
 }
 
 impl Class {
@@ -674,7 +793,7 @@ impl Class {
                 )
             }).collect(),
         });
-        let callback_data = Box::new(ClassCallbackData {
+        let callback_data = Rc::new(ClassCallbackData {
             callbacks,
         });
         
@@ -714,24 +833,25 @@ impl Class {
             .map_err(|_| Error::InvalidParameter("Class name contains null bytes"))?;
         
         // Create the JSClassDefinition
-        let mut def = ffi::JSClassDefinition {
+        let def = ffi::JSClassDefinition {
             version: 0,
             attributes: definition.attributes.as_raw(),
             className: class_name.as_ptr(),
             parentClass: definition.parent_class.map_or(ptr::null_mut(), |c| c.raw),
             staticValues: if static_values.len() > 1 { static_values.as_ptr() } else { ptr::null() },
             staticFunctions: if static_functions.len() > 1 { static_functions.as_ptr() } else { ptr::null() },
-            initialize: if definition.initialize.is_some() { Some(initialize_callback) } else { None },
-            finalize: if definition.finalize.is_some() { Some(finalize_callback) } else { None },
-            hasProperty: if definition.has_property.is_some() { Some(has_property_callback) } else { None },
-            getProperty: if definition.get_property.is_some() { Some(get_property_callback) } else { None },
-            setProperty: if definition.set_property.is_some() { Some(set_property_callback) } else { None },
-            deleteProperty: if definition.delete_property.is_some() { Some(delete_property_callback) } else { None },
-            getPropertyNames: if definition.get_property_names.is_some() { Some(get_property_names_callback) } else { None },
-            callAsFunction: if definition.call_as_function.is_some() { Some(call_as_function_callback) } else { None },
-            callAsConstructor: if definition.call_as_constructor.is_some() { Some(call_as_constructor_callback) } else { None },
-            hasInstance: if definition.has_instance.is_some() { Some(has_instance_callback) } else { None },
-            convertToType: if definition.convert_to_type.is_some() { Some(convert_to_type_callback) } else { None },
+            initialize: if callback_data.callbacks.initialize.is_some() { Some(initialize_callback) } else { None },
+            // Always finalize so each object releases its reference to the callback data
+            finalize: Some(finalize_callback),
+            hasProperty: if callback_data.callbacks.has_property.is_some() { Some(has_property_callback) } else { None },
+            getProperty: if callback_data.callbacks.get_property.is_some() { Some(get_property_callback) } else { None },
+            setProperty: if callback_data.callbacks.set_property.is_some() { Some(set_property_callback) } else { None },
+            deleteProperty: if callback_data.callbacks.delete_property.is_some() { Some(delete_property_callback) } else { None },
+            getPropertyNames: if callback_data.callbacks.get_property_names.is_some() { Some(get_property_names_callback) } else { None },
+            callAsFunction: if callback_data.callbacks.call_as_function.is_some() { Some(call_as_function_callback) } else { None },
+            callAsConstructor: if callback_data.callbacks.call_as_constructor.is_some() { Some(call_as_constructor_callback) } else { None },
+            hasInstance: if callback_data.callbacks.has_instance.is_some() { Some(has_instance_callback) } else { None },
+            convertToType: if callback_data.callbacks.convert_to_type.is_some() { Some(convert_to_type_callback) } else { None },
         };
         
         // Create the JS class
@@ -741,18 +861,39 @@ impl Class {
             return Err(Error::JSError("Failed to create JavaScript class".to_string()));
         }
         
-        // Store the callback data in a Box that will be leaked and later freed in the finalize callback
-        let leaked_data = Box::into_raw(callback_data);
-        
-        // We need to store the callback data somewhere associated with the class
-        // In a real implementation, we would maintain a global registry of class data
+        CLASS_CALLBACK_DATA.with(|data| {
+            data.borrow_mut().insert(Rc::as_ptr(&callback_data) as usize);
+        });
         
-        Ok(Class { raw })
+        Ok(Class { raw, data: Some(callback_data) })
     }
     
-    /// Create a new class from a raw JSClassRef.
-    pub(crate) unsafe fn from_raw(raw: ffi::JSClassRef) -> Self {
-        Class { raw }
+    /// Create a constructor for this class that works with `new` in JavaScript.
+    ///
+    /// The constructor's `prototype` property is set to the class prototype, so
+    /// objects created with `Object::with_class` inherit the class's static values
+    /// and functions. The callback is freed once the constructor is garbage collected.
+    pub fn make_constructor<'a>(&self, context: &Context<'a>, callback: CallAsConstructorCallback) -> Object<'a> {
+        unsafe {
+            let raw = ffi::JSObjectMakeConstructor(context.as_raw(), self.raw, Some(class_constructor_callback));
+            let callback = Rc::new(callback);
+            CONSTRUCTOR_CALLBACKS.with(|callbacks| {
+                callbacks.borrow_mut().insert(raw as usize, Rc::downgrade(&callback));
+            });
+            
+            // Tie the callback's lifetime to the constructor through a hidden property
+            let keeper = Box::new(ConstructorKeeper { constructor: raw as usize, callback });
+            let keeper = CONSTRUCTOR_KEEPER_CLASS.with(|class| {
+                ffi::JSObjectMake(context.as_raw(), *class, Box::into_raw(keeper) as *mut c_void)
+            });
+            let constructor = Object::from_raw(context.clone(), raw);
+            let key = Value::symbol(context, Some("constructor callback"));
+            let attributes = PropertyAttributes::READ_ONLY | PropertyAttributes::DONT_ENUM | PropertyAttributes::DONT_DELETE;
+            // Defining a property on a new constructor can't throw
+            let _ = constructor.set_property_for_key(key, Value::from_raw(context, keeper), attributes);
+            
+            constructor
+        }
     }
     
     /// Get a reference to the raw JSClassRef.
@@ -773,7 +914,106 @@ impl Clone for Class {
     fn clone(&self) -> Self {
         unsafe {
             let raw = ffi::JSClassRetain(self.raw);
-            Class { raw }
+            Class { raw, data: self.data.clone() }
+        }
+    }
+}
+
+thread_local! {
+    // The class shared by all host objects, created on first use.
+    static HOST_OBJECT_CLASS: ffi::JSClassRef = unsafe {
+        let definition = ffi::JSClassDefinition {
+            version: 0,
+            attributes: 0,
+            className: c"HostObject".as_ptr(),
+            parentClass: ptr::null_mut(),
+            staticValues: ptr::null(),
+            staticFunctions: ptr::null(),
+            initialize: None,
+            finalize: Some(host_object_finalize),
+            hasProperty: None,
+            getProperty: None,
+            setProperty: None,
+            deleteProperty: None,
+            getPropertyNames: None,
+            callAsFunction: None,
+            callAsConstructor: None,
+            hasInstance: None,
+            convertToType: None,
+        };
+        ffi::JSClassCreate(&definition)
+    };
+}
+
+extern "C" fn host_object_finalize(object: ffi::JSObjectRef) {
+    unsafe {
+        let data = ffi::JSObjectGetPrivate(object) as *mut Box<dyn Any>;
+        if !data.is_null() {
+            drop(Box::from_raw(data));
+        }
+    }
+}
+
+/// A JavaScript object backed by a Rust value.
+///
+/// The value is stored as the object's private data and dropped when the object
+/// is garbage collected or its context is destroyed, so `Drop for T` always runs
+/// exactly once. Inside callbacks, `downcast_ref` retrieves the value from the
+/// object again.
+pub struct HostObject<T> {
+    _phantom: PhantomData<T>,
+}
+
+impl<T: 'static> HostObject<T> {
+    /// Creates a JavaScript object that owns a Rust value.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context in which to create the object.
+    /// * `value` - The Rust value to attach to the object.
+    ///
+    /// # Returns
+    ///
+    /// The new JavaScript object.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<'a>(context: &Context<'a>, value: T) -> Object<'a> {
+        let data: Box<Box<dyn Any>> = Box::new(Box::new(value));
+        
+        unsafe {
+            let raw = ffi::JSObjectMake(
+                context.as_raw(),
+                HOST_OBJECT_CLASS.with(|class| *class),
+                Box::into_raw(data) as *mut c_void,
+            );
+            Object::from_raw(context.clone(), raw)
+        }
+    }
+    
+    /// Returns the Rust value attached to a host object.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The object to inspect.
+    ///
+    /// # Returns
+    ///
+    /// The attached value, or None if the object is not a host object or holds a
+    /// value of another type.
+    pub fn downcast_ref<'o>(object: &'o Object<'_>) -> Option<&'o T> {
+        unsafe {
+            let is_host_object = HOST_OBJECT_CLASS.with(|class| {
+                ffi::JSValueIsObjectOfClass(object.context.as_raw(), object.raw, *class)
+            });
+            if !is_host_object {
+                return None;
+            }
+            
+            let data = ffi::JSObjectGetPrivate(object.raw) as *const Box<dyn Any>;
+            if data.is_null() {
+                return None;
+            }
+            
+            (*data).downcast_ref::<T>()
         }
     }
 }
@@ -796,13 +1036,35 @@ impl<'a> Object<'a> {
         }
     }
     
+    /// Create a new JavaScript object populated from name/value pairs.
+    ///
+    /// Each property is set with `PropertyAttributes::NONE`. Setting stops at the
+    /// first property that throws, and the error is returned.
+    pub fn from_pairs<'n, I>(context: &Context<'a>, pairs: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (&'n str, Value<'a>)>,
+    {
+        let object = Self::new(context);
+        object.set_many(pairs)?;
+        Ok(object)
+    }
+    
     /// Create a new JavaScript object with a specific class.
+    ///
+    /// Without `private_data`, the object holds a reference to the class callbacks so
+    /// they stay alive until it is finalized. Passing `private_data` replaces them, and
+    /// the class callbacks are not invoked for the object.
     pub fn with_class(context: &Context<'a>, class: &Class, private_data: Option<*mut c_void>) -> Self {
         unsafe {
+            let private_data = private_data.unwrap_or_else(|| {
+                class.data.as_ref().map_or(ptr::null_mut(), |data| {
+                    Rc::into_raw(Rc::clone(data)) as *mut c_void
+                })
+            });
             let raw = ffi::JSObjectMake(
                 context.as_raw(),
                 class.as_raw(),
-                private_data.unwrap_or(ptr::null_mut()),
+                private_data,
             );
             Object {
                 context: context.clone(),
@@ -916,9 +1178,9 @@ impl<'a> Object<'a> {
         unsafe {
             let mut exception = ptr::null();
             
-            let name_string = name.map(|n| String::new(n));
+            let name_string = name.map(String::new);
             let body_string = String::new(body);
-            let source_url_string = source_url.map(|url| String::new(url));
+            let source_url_string = source_url.map(String::new);
             
             let param_strings: Vec<String> = parameters.iter().map(|&p| String::new(p)).collect();
             let param_ptrs: Vec<ffi::JSStringRef> = param_strings.iter().map(|s| s.as_raw()).collect();
@@ -952,10 +1214,10 @@ impl<'a> Object<'a> {
     /// Create a JavaScript function with a callback.
     pub fn function_with_callback<F>(context: &Context<'a>, name: Option<&str>, callback: F) -> Self
     where
-        F: Fn(&Context, &Object, Option<&Object>, &[Value]) -> Result<Value> + 'static,
+        F: for<'c> Fn(&Context<'c>, &Object<'c>, Option<&Object<'c>>, &[Value<'c>]) -> Result<Value<'c>> + 'static,
     {
         unsafe {
-            let callback_box: Box<dyn Fn(&Context, &Object, Option<&Object>, &[Value]) -> Result<Value>> = Box::new(callback);
+            let callback_box: CallAsFunctionCallback = Box::new(callback);
             let callback_ptr = Box::into_raw(Box::new(callback_box));
             
             extern "C" fn trampoline(
@@ -968,14 +1230,14 @@ impl<'a> Object<'a> {
             ) -> ffi::JSValueRef {
                 unsafe {
                     let context = Context::from_raw(ctx);
-                    let func = Object::from_raw(context, function);
+                    let func = Object::from_raw(context.clone(), function);
                     let this = if this_object.is_null() {
                         None
                     } else {
-                        Some(Object::from_raw(context, this_object))
+                        Some(Object::from_raw(context.clone(), this_object))
                     };
                     
-                    let callback_ptr = ffi::JSObjectGetPrivate(function) as *mut Box<dyn Fn(&Context, &Object, Option<&Object>, &[Value]) -> Result<Value>>;
+                    let callback_ptr = ffi::JSObjectGetPrivate(function) as *mut CallAsFunctionCallback;
                     let callback = &**callback_ptr;
                     
                     let args = if argument_count == 0 || arguments.is_null() {
@@ -983,7 +1245,7 @@ impl<'a> Object<'a> {
                     } else {
                         let args_slice = std::slice::from_raw_parts(arguments, argument_count);
                         args_slice.iter()
-                            .map(|&arg| Value::from_raw(context, arg))
+                            .map(|&arg| Value::from_raw(&context, arg))
                             .collect::<Vec<_>>()
                     };
                     
@@ -1002,7 +1264,7 @@ impl<'a> Object<'a> {
             
             extern "C" fn finalize(object: ffi::JSObjectRef) {
                 unsafe {
-                    let callback_ptr = ffi::JSObjectGetPrivate(object) as *mut Box<dyn Fn(&Context, &Object, Option<&Object>, &[Value]) -> Result<Value>>;
+                    let callback_ptr = ffi::JSObjectGetPrivate(object) as *mut CallAsFunctionCallback;
                     if !callback_ptr.is_null() {
                         drop(Box::from_raw(callback_ptr));
                     }
@@ -1013,7 +1275,7 @@ impl<'a> Object<'a> {
             let class_definition = ffi::JSClassDefinition {
                 version: 0,
                 attributes: 0,
-                className: b"RustFunctionCallback\0".as_ptr() as *const c_char,
+                className: c"RustFunctionCallback".as_ptr(),
                 parentClass: ptr::null_mut(),
                 staticValues: ptr::null(),
                 staticFunctions: ptr::null(),
@@ -1032,7 +1294,7 @@ impl<'a> Object<'a> {
             
             let class = ffi::JSClassCreate(&class_definition);
             
-            let name_string = name.map(|n| String::new(n));
+            let name_string = name.map(String::new);
             
             let raw = ffi::JSObjectMakeFunctionWithCallback(
                 context.as_raw(),
@@ -1092,13 +1354,15 @@ impl<'a> Object<'a> {
     /// This should only be used in finalize callbacks.
     unsafe fn from_raw_no_context(raw: ffi::JSObjectRef) -> Self {
         Object {
-            context: Context::dummy(),
+            context: unsafe { Context::dummy() },
             raw,
         }
     }
     
     /// Convert a Value to an Object if possible.
-    pub fn from_value(value: Value<'a>) -> Result<Self> {
+    ///
+    /// The value is borrowed, so it remains usable if the conversion fails.
+    pub fn from_value(value: &Value<'a>) -> Result<Self> {
         if !value.is_object() {
             return Err(Error::InvalidType("Value is not an object".to_string()));
         }
@@ -1160,6 +1424,19 @@ impl<'a> Object<'a> {
         }
     }
     
+    /// Check if this object has an own property with the given name.
+    ///
+    /// Unlike `has_property`, this ignores properties inherited through the
+    /// prototype chain, as `Object.prototype.hasOwnProperty` does.
+    pub fn has_own_property(&self, name: &str) -> Result<bool> {
+        let object_ctor = self.context.global_object().get_property("Object")?.into_object()?;
+        let prototype = object_ctor.get_property("prototype")?.into_object()?;
+        let has_own_property = prototype.get_property("hasOwnProperty")?.into_object()?;
+        
+        let result = has_own_property.call(Some(self), &[Value::string(&self.context, name)])?;
+        Ok(result.to_boolean())
+    }
+    
     /// Get a property value by name.
     pub fn get_property(&self, name: &str) -> Result<Value<'a>> {
         let name_string = String::new(name);
@@ -1202,6 +1479,97 @@ impl<'a> Object<'a> {
         }
     }
     
+    /// Set several property values by name.
+    ///
+    /// Each property is set with `PropertyAttributes::NONE`, in iteration order.
+    /// Setting stops at the first property that throws, and the error is returned;
+    /// properties set before it are kept.
+    pub fn set_many<'n, I>(&self, pairs: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (&'n str, Value<'a>)>,
+    {
+        self.set_properties(pairs, PropertyAttributes::NONE)
+    }
+    
+    /// Set several property values by name with the same attributes.
+    ///
+    /// Properties are set in iteration order. Setting stops at the first property
+    /// that throws, and the error is returned; properties set before it are kept.
+    /// Each property still takes its own call into JavaScriptCore, but callers get
+    /// a single call site and error path.
+    pub fn set_properties<'n, I>(&self, properties: I, attributes: PropertyAttributes) -> Result<()>
+    where
+        I: IntoIterator<Item = (&'n str, Value<'a>)>,
+    {
+        for (name, value) in properties {
+            self.set_property(name, value, attributes)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Define an accessor (getter/setter) property, as `Object.defineProperty` would.
+    ///
+    /// `DONT_ENUM` makes the property non-enumerable and `DONT_DELETE` makes it
+    /// non-configurable. `READ_ONLY` does not apply to accessors and is ignored.
+    pub fn define_accessor(&self, name: &str, getter: Option<Value<'a>>, setter: Option<Value<'a>>, attributes: PropertyAttributes) -> Result<()> {
+        let descriptor = Object::new(&self.context);
+
+        if let Some(getter) = getter {
+            descriptor.set_property("get", getter, PropertyAttributes::NONE)?;
+        }
+        if let Some(setter) = setter {
+            descriptor.set_property("set", setter, PropertyAttributes::NONE)?;
+        }
+
+        let enumerable = !attributes.is_dont_enum();
+        let configurable = !attributes.is_dont_delete();
+        descriptor.set_property("enumerable", Value::boolean(&self.context, enumerable), PropertyAttributes::NONE)?;
+        descriptor.set_property("configurable", Value::boolean(&self.context, configurable), PropertyAttributes::NONE)?;
+
+        self.define_property_with_descriptor(name, &descriptor)
+    }
+
+    /// Define a data property with explicit `writable`, `enumerable` and `configurable` flags.
+    pub fn define_value_property(&self, name: &str, value: Value<'a>, writable: bool, enumerable: bool, configurable: bool) -> Result<()> {
+        let descriptor = Object::new(&self.context);
+        descriptor.set_property("value", value, PropertyAttributes::NONE)?;
+        descriptor.set_property("writable", Value::boolean(&self.context, writable), PropertyAttributes::NONE)?;
+        descriptor.set_property("enumerable", Value::boolean(&self.context, enumerable), PropertyAttributes::NONE)?;
+        descriptor.set_property("configurable", Value::boolean(&self.context, configurable), PropertyAttributes::NONE)?;
+
+        self.define_property_with_descriptor(name, &descriptor)
+    }
+
+    /// Define a data property using `Object.defineProperty` semantics.
+    ///
+    /// Unlike `set_property`, this redefines an existing property rather than
+    /// assigning to it, so setters on the prototype chain are not invoked.
+    pub fn define_data_property(&self, name: &str, value: Value<'a>, attributes: PropertyAttributes) -> Result<()> {
+        self.define_value_property(
+            name,
+            value,
+            !attributes.is_read_only(),
+            !attributes.is_dont_enum(),
+            !attributes.is_dont_delete(),
+        )
+    }
+
+    /// Invoke the global `Object.defineProperty` with a descriptor object.
+    fn define_property_with_descriptor(&self, name: &str, descriptor: &Object<'a>) -> Result<()> {
+        let object_ctor = self.context.global_object().get_property("Object")?.into_object()?;
+        let define_property = object_ctor.get_property("defineProperty")?.into_object()?;
+
+        let arguments = [
+            self.to_value(),
+            Value::string(&self.context, name),
+            descriptor.to_value(),
+        ];
+        define_property.call(Some(&object_ctor), &arguments)?;
+
+        Ok(())
+    }
+
     /// Delete a property by name.
     pub fn delete_property(&self, name: &str) -> Result<bool> {
         let name_string = String::new(name);
@@ -1241,6 +1609,25 @@ impl<'a> Object<'a> {
         }
     }
     
+    /// Get an element by index, distinguishing absent elements from `undefined` ones.
+    ///
+    /// Returns `Ok(None)` if the index is not below the object's `length`, or if the
+    /// element is a hole in a sparse array, and `Ok(Some(value))` otherwise, even when
+    /// the element is explicitly `undefined`. Objects without a numeric `length`
+    /// have no elements. Use `get_property_at_index` to read the raw property.
+    pub fn try_get_at(&self, index: u32) -> Result<Option<Value<'a>>> {
+        let length = self.get_property("length")?.to_number()?;
+        if length.is_nan() || f64::from(index) >= length {
+            return Ok(None);
+        }
+        
+        if !self.has_own_property(&index.to_string())? {
+            return Ok(None);
+        }
+        
+        self.get_property_at_index(index).map(Some)
+    }
+    
     /// Set a property value by numeric index.
     pub fn set_property_at_index(&self, index: u32, value: Value<'a>) -> Result<()> {
         unsafe {
@@ -1328,7 +1715,12 @@ impl<'a> Object<'a> {
     }
     
     /// Set private data on this object.
-    pub fn set_private(&self, data: *mut c_void) -> bool {
+    ///
+    /// # Safety
+    ///
+    /// `data` replaces any pointer the object's class finalizer expects, so it must
+    /// be of the type that class stores, or null.
+    pub unsafe fn set_private(&self, data: *mut c_void) -> bool {
         unsafe {
             ffi::JSObjectSetPrivate(self.raw, data)
         }
@@ -1383,6 +1775,10 @@ impl<'a> Object<'a> {
     }
     
     /// Get all property names of this object.
+    ///
+    /// Only enumerable, string-keyed properties are returned, including those
+    /// inherited through the prototype chain. Use `own_property_keys` to also see
+    /// symbol keys and non-enumerable properties.
     pub fn get_property_names(&self) -> Result<Vec<String>> {
         unsafe {
             let names_array = ffi::JSObjectCopyPropertyNames(self.context.as_raw(), self.raw);
@@ -1404,6 +1800,60 @@ impl<'a> Object<'a> {
         }
     }
     
+    /// Get all own property keys of this object, as `Reflect.ownKeys` does.
+    ///
+    /// Unlike `get_property_names`, this includes symbol keys and non-enumerable
+    /// properties but skips inherited ones. Keys are returned as Values since
+    /// symbols are not strings.
+    pub fn own_property_keys(&self) -> Result<Vec<Value<'a>>> {
+        let reflect = self.context.global_object().get_property("Reflect")?.into_object()?;
+        reflect.invoke("ownKeys", &[self.to_value()])?.to_vec()
+    }
+    
+    /// Get a lazy iterator over the elements of this array.
+    ///
+    /// Returns `Error::InvalidType` if this object is not an array.
+    pub fn array_iter(&self) -> Result<ArrayIter<'a>> {
+        self.to_value().array_iter()
+    }
+    
+    /// Collect the elements of this array into a vector.
+    ///
+    /// Returns `Error::InvalidType` if this object is not an array.
+    pub fn as_array_vec(&self) -> Result<Vec<Value<'a>>> {
+        self.to_value().to_vec()
+    }
+    
+    /// Collect the entries of this object by calling its `entries()` method.
+    ///
+    /// For a `Map` this returns its key-value pairs in insertion order. For a `Set`
+    /// each value is returned as both key and value, and for an array the keys are
+    /// the indices.
+    ///
+    /// Returns `Error::InvalidType` if the object has no callable `entries` method or
+    /// the iterator does not yield `[key, value]` pairs.
+    pub fn map_entries(&self) -> Result<Vec<(Value<'a>, Value<'a>)>> {
+        let iterator = self.invoke("entries", &[])?.to_object()?;
+        let mut entries = Vec::new();
+        
+        loop {
+            let result = iterator.invoke("next", &[])?.to_object()?;
+            if result.get_property("done")?.to_boolean() {
+                break;
+            }
+            
+            let entry = result.get_property("value")?;
+            if !entry.is_array() {
+                return Err(Error::InvalidType("Iterator entry is not a [key, value] pair".to_string()));
+            }
+            
+            let entry = Object::from_value(&entry)?;
+            entries.push((entry.get_property_at_index(0)?, entry.get_property_at_index(1)?));
+        }
+        
+        Ok(entries)
+    }
+    
     /// Check if this object is a function.
     pub fn is_function(&self) -> bool {
         unsafe {
@@ -1444,6 +1894,24 @@ impl<'a> Object<'a> {
         }
     }
     
+    /// Call a method on this object by name, passing this object as `this`.
+    ///
+    /// Returns `Error::InvalidType` if the property is not callable.
+    pub fn invoke(&self, method_name: &str, arguments: &[Value<'a>]) -> Result<Value<'a>> {
+        let method = self.get_property(method_name)?;
+        
+        if !method.is_object() {
+            return Err(Error::InvalidType(format!("Property '{}' is not a function", method_name)));
+        }
+        
+        let method = Object::from_value(&method)?;
+        if !method.is_function() {
+            return Err(Error::InvalidType(format!("Property '{}' is not a function", method_name)));
+        }
+        
+        method.call(Some(self), arguments)
+    }
+    
     /// Check if this object is a constructor.
     pub fn is_constructor(&self) -> bool {
         unsafe {
@@ -1508,9 +1976,9 @@ impl<'a> Object<'a> {
     /// Get the prototype object of this class instance.
     pub fn get_constructor_prototype(&self) -> Result<Object<'a>> {
         let constructor = self.get_property("constructor")?;
-        let constructor_obj = Object::from_value(constructor)?;
+        let constructor_obj = Object::from_value(&constructor)?;
         let prototype = constructor_obj.get_property("prototype")?;
-        Object::from_value(prototype)
+        Object::from_value(&prototype)
     }
     
     /// If this object is a Proxy, get its target.
@@ -1541,16 +2009,30 @@ impl<'a> Object<'a> {
     }
 }
 
-impl<'a> From<Object<'a>> for Value<'a> {
-    fn from(obj: Object<'a>) -> Self {
-        obj.to_value()
-    }
-}
-
 impl<'a> TryFrom<Value<'a>> for Object<'a> {
     type Error = Error;
     
     fn try_from(value: Value<'a>) -> Result<Self> {
-        Object::from_value(value)
+        value.into_object()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn property_attributes_contain_their_combined_flags() {
+        let attributes = PropertyAttributes::READ_ONLY | PropertyAttributes::DONT_DELETE;
+        assert!(attributes.contains(PropertyAttributes::READ_ONLY));
+        assert!(attributes.contains(PropertyAttributes::DONT_DELETE));
+        assert!(attributes.contains(PropertyAttributes::READ_ONLY | PropertyAttributes::DONT_DELETE));
+        assert!(attributes.contains(PropertyAttributes::NONE));
+        assert!(!attributes.contains(PropertyAttributes::DONT_ENUM));
+        assert!(!attributes.contains(PropertyAttributes::READ_ONLY | PropertyAttributes::DONT_ENUM));
+        
+        assert!(attributes.is_read_only());
+        assert!(!attributes.is_dont_enum());
+        assert!(attributes.is_dont_delete());
+        assert_eq!(PropertyAttributes::new(true, false, true), attributes);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,119 @@
+//! JavaScript regular expressions.
+//!
+//! This module provides a thin wrapper around JavaScript RegExp objects, allowing
+//! patterns to be compiled by the JavaScript engine and matched from Rust code
+//! using the same semantics as scripts running in the context.
+
+use crate::javascript_core::context::Context;
+use crate::javascript_core::exception::{Error, Result};
+use crate::javascript_core::object::Object;
+use crate::javascript_core::value::Value;
+
+/// A JavaScript regular expression.
+///
+/// The RegExp struct wraps a JavaScript RegExp object. Matching is performed by
+/// calling the object's own `test` and `exec` methods, so flags such as `g` and
+/// `y` update `lastIndex` exactly as they would in JavaScript.
+pub struct RegExp<'a> {
+    /// The underlying JavaScript object.
+    object: Object<'a>,
+}
+
+impl<'a> RegExp<'a> {
+    /// Creates a new regular expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context in which to create the regular expression.
+    /// * `pattern` - The regular expression pattern.
+    /// * `flags` - The flags, such as "g" or "i".
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the new RegExp, or the JavaScript SyntaxError if the pattern is invalid.
+    pub fn new(context: &Context<'a>, pattern: &str, flags: &str) -> Result<Self> {
+        let object = Object::regexp(context, pattern, flags)?;
+        Ok(RegExp { object })
+    }
+    
+    /// Creates a RegExp from an existing JavaScript object.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The JavaScript object to wrap.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the RegExp, or an error if the object is not a RegExp.
+    pub fn from_object(object: Object<'a>) -> Result<Self> {
+        if !object.to_value().is_instance_of_named("RegExp")? {
+            return Err(Error::InvalidType("Object is not a RegExp".to_string()));
+        }
+        
+        Ok(RegExp { object })
+    }
+    
+    /// Tests whether the regular expression matches the input.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The string to test.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `true` if the input matches, `false` otherwise.
+    pub fn test(&self, input: &str) -> Result<bool> {
+        let input = Value::string(self.object.context(), input);
+        let result = self.object.invoke("test", &[input])?;
+        Ok(result.to_boolean())
+    }
+    
+    /// Executes the regular expression against the input.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The string to search.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the full match followed by each capture group, or None if
+    /// there is no match. Unmatched capture groups are returned as empty strings.
+    pub fn exec(&self, input: &str) -> Result<Option<Vec<std::string::String>>> {
+        let input = Value::string(self.object.context(), input);
+        let result = self.object.invoke("exec", &[input])?;
+        
+        if result.is_null() {
+            return Ok(None);
+        }
+        
+        let mut captures = Vec::new();
+        for item in result.array_iter()? {
+            let item = item?;
+            if item.is_undefined() {
+                captures.push(std::string::String::new());
+            } else {
+                captures.push(item.to_string()?.to_string());
+            }
+        }
+        
+        Ok(Some(captures))
+    }
+    
+    /// Gets the underlying JavaScript object.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the underlying JavaScript object.
+    pub fn as_object(&self) -> &Object<'a> {
+        &self.object
+    }
+    
+    /// Converts this regular expression to a JavaScript value.
+    ///
+    /// # Returns
+    ///
+    /// A JavaScript value representing this regular expression.
+    pub fn to_value(&self) -> Value<'a> {
+        self.object.to_value()
+    }
+}
@@ -6,13 +6,11 @@
 //! JavaScriptCore, with methods for conversion to and from Rust strings.
 
 use std::ffi::{CStr, CString};
-use std::ptr;
 use std::str;
 use std::fmt;
-use std::ops::Deref;
 
 use crate::javascript_core::ffi;
-use crate::javascript_core::error::{Error, Result};
+use crate::javascript_core::exception::Result;
 
 /// A JavaScript string.
 ///
@@ -35,7 +33,8 @@ impl String {
     ///
     /// # Returns
     ///
-    /// A new String instance representing the converted string.
+    /// A new String instance representing the converted string. If `s` contains
+    /// a null byte, the result is an empty string; use `try_from_str` to keep it.
     pub fn new(s: &str) -> Self {
         unsafe {
             let c_string = CString::new(s).unwrap_or_else(|_| CString::new("").unwrap());
@@ -44,6 +43,25 @@ impl String {
         }
     }
     
+    /// Creates a new JavaScript string from a Rust string, keeping null bytes.
+    ///
+    /// Unlike `new`, this never silently replaces the input. The string is built
+    /// from UTF-16 code units, so no intermediate C string is involved and interior
+    /// null bytes become part of the JavaScript string.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The Rust string to convert.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the new String. Building a string from UTF-16 can't
+    /// fail, so this is always `Ok`; it matches `ul::String::try_from_str`.
+    pub fn try_from_str(s: &str) -> Result<Self> {
+        let chars: Vec<u16> = s.encode_utf16().collect();
+        Ok(String::from_chars(&chars))
+    }
+    
     /// Creates a JavaScript string from raw UTF-16 characters.
     ///
     /// This method creates a new JSStringRef from an array of UTF-16 code units,
@@ -103,6 +121,11 @@ impl String {
     
     /// Returns the length of the string in UTF-16 code units.
     ///
+    /// This is the same value JavaScript reports for `string.length`, so characters
+    /// outside the Basic Multilingual Plane (such as most emoji) count as two. Use
+    /// `byte_len` for the UTF-8 length or `chars().count()` for the number of
+    /// Unicode scalar values.
+    ///
     /// # Returns
     ///
     /// The number of UTF-16 code units in the string.
@@ -110,6 +133,30 @@ impl String {
         unsafe { ffi::JSStringGetLength(self.raw) }
     }
     
+    /// Returns the length of the string in bytes when encoded as UTF-8.
+    ///
+    /// Unpaired surrogates count as the three bytes of the replacement character.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes in the UTF-8 representation of the string.
+    pub fn byte_len(&self) -> usize {
+        self.chars().map(char::len_utf8).sum()
+    }
+    
+    /// Returns an iterator over the Unicode characters of the string.
+    ///
+    /// The UTF-16 code units are decoded with `char::decode_utf16`. Malformed
+    /// surrogate pairs yield `char::REPLACEMENT_CHARACTER` instead of failing.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the characters of the string.
+    pub fn chars(&self) -> impl Iterator<Item = char> {
+        char::decode_utf16(self.to_chars())
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+    
     /// Checks if the string is empty.
     ///
     /// # Returns
@@ -122,12 +169,13 @@ impl String {
     /// Converts the string to a Rust String.
     ///
     /// This method converts the JavaScript string to a Rust String, handling
-    /// the encoding conversion from UTF-16 to UTF-8.
+    /// the encoding conversion from UTF-16 to UTF-8. It backs the `Display`
+    /// implementation, so callers use `to_string()`.
     ///
     /// # Returns
     ///
     /// A Rust String containing the same text as this JavaScript string.
-    pub fn to_string(&self) -> std::string::String {
+    fn to_utf8(&self) -> std::string::String {
         unsafe {
             let max_size = ffi::JSStringGetMaximumUTF8CStringSize(self.raw);
             let mut buffer = vec![0u8; max_size];
@@ -142,7 +190,7 @@ impl String {
             buffer.truncate(actual_size - 1);
             
             // Convert the buffer to a Rust String, replacing invalid UTF-8 sequences
-            String::from_utf8_lossy(&buffer).into_owned()
+            std::string::String::from_utf8_lossy(&buffer).into_owned()
         }
     }
     
@@ -165,18 +213,6 @@ impl String {
         }
     }
     
-    /// Returns a C-style string pointer for interfacing with C APIs.
-    ///
-    /// This method is primarily used for internal conversions when interacting
-    /// with C APIs that expect null-terminated strings.
-    ///
-    /// # Returns
-    ///
-    /// A CString containing the UTF-8 representation of this string.
-    pub(crate) fn as_c_str(&self) -> CString {
-        CString::new(self.to_string()).unwrap_or_else(|_| CString::new("").unwrap())
-    }
-    
     /// Tests if this string is equal to another JavaScript string.
     ///
     /// # Arguments
@@ -239,24 +275,13 @@ impl Clone for String {
 
 impl fmt::Debug for String {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "JSString({:?})", self.to_string())
+        write!(f, "JSString({:?})", self.to_utf8())
     }
 }
 
 impl fmt::Display for String {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
-    }
-}
-
-impl Deref for String {
-    type Target = str;
-    
-    fn deref(&self) -> &Self::Target {
-        // This is not ideal as we're creating a temporary string
-        // A more efficient implementation would cache the result
-        static EMPTY: &str = "";
-        EMPTY
+        f.write_str(&self.to_utf8())
     }
 }
 
@@ -294,4 +319,27 @@ impl From<String> for std::string::String {
     fn from(s: String) -> Self {
         s.to_string()
     }
+}
+
+impl From<&String> for crate::ul::String {
+    /// Converts a JavaScript string into an Ultralight string.
+    ///
+    /// The conversion goes through UTF-16 code units directly, so the text is not
+    /// re-encoded through a Rust UTF-8 string first.
+    fn from(s: &String) -> Self {
+        crate::ul::String::from_utf16(&s.to_chars())
+    }
+}
+
+impl From<&crate::ul::String> for String {
+    /// Converts an Ultralight string into a JavaScript string.
+    ///
+    /// Ultralight strings store UTF-8, so the data is transcoded to UTF-16 code
+    /// units, replacing any invalid UTF-8 sequences.
+    fn from(s: &crate::ul::String) -> Self {
+        let chars: Vec<u16> = std::string::String::from_utf8_lossy(s.as_bytes())
+            .encode_utf16()
+            .collect();
+        String::from_chars(&chars)
+    }
 }
\ No newline at end of file
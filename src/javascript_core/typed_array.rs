@@ -6,12 +6,13 @@
 //! numeric array types available in JavaScript, with methods for creation,
 //! manipulation, and data access.
 
-use std::marker::PhantomData;
+use std::any::TypeId;
+use std::mem;
 use std::ptr;
 use std::os::raw::c_void;
 
 use crate::javascript_core::context::Context;
-use crate::javascript_core::error::{Error, Result};
+use crate::javascript_core::exception::{Error, Result};
 use crate::javascript_core::ffi;
 use crate::javascript_core::object::Object;
 use crate::javascript_core::value::Value;
@@ -47,7 +48,7 @@ pub enum TypedArrayType {
 
 impl TypedArrayType {
     /// Converts the Rust TypedArrayType enum to the FFI JSTypedArrayType enum.
-    fn to_ffi(&self) -> ffi::JSTypedArrayType {
+    fn to_ffi(self) -> ffi::JSTypedArrayType {
         match self {
             TypedArrayType::Int8Array => ffi::JSTypedArrayType::kJSTypedArrayTypeInt8Array,
             TypedArrayType::Int16Array => ffi::JSTypedArrayType::kJSTypedArrayTypeInt16Array,
@@ -93,6 +94,40 @@ impl TypedArrayType {
             TypedArrayType::ArrayBuffer => 1, // ArrayBuffer has byte-level access
         }
     }
+
+    /// Returns the typed array type whose elements are the Rust type `T`.
+    ///
+    /// # Returns
+    ///
+    /// The matching type for `i8`, `u8`, `i16`, `u16`, `i32`, `u32`, `f32`, `f64`,
+    /// `i64` and `u64`, or `None` for any other type.
+    pub fn for_rust<T: 'static>() -> Option<Self> {
+        let id = TypeId::of::<T>();
+        
+        if id == TypeId::of::<i8>() {
+            Some(TypedArrayType::Int8Array)
+        } else if id == TypeId::of::<u8>() {
+            Some(TypedArrayType::Uint8Array)
+        } else if id == TypeId::of::<i16>() {
+            Some(TypedArrayType::Int16Array)
+        } else if id == TypeId::of::<u16>() {
+            Some(TypedArrayType::Uint16Array)
+        } else if id == TypeId::of::<i32>() {
+            Some(TypedArrayType::Int32Array)
+        } else if id == TypeId::of::<u32>() {
+            Some(TypedArrayType::Uint32Array)
+        } else if id == TypeId::of::<f32>() {
+            Some(TypedArrayType::Float32Array)
+        } else if id == TypeId::of::<f64>() {
+            Some(TypedArrayType::Float64Array)
+        } else if id == TypeId::of::<i64>() {
+            Some(TypedArrayType::BigInt64Array)
+        } else if id == TypeId::of::<u64>() {
+            Some(TypedArrayType::BigUint64Array)
+        } else {
+            None
+        }
+    }
 }
 
 /// A safe wrapper around a JavaScript typed array.
@@ -145,6 +180,27 @@ impl<'a> TypedArray<'a> {
         }
     }
     
+    /// Creates a new typed array holding a copy of a Rust slice.
+    ///
+    /// The typed array type is picked from the element type with
+    /// `TypedArrayType::for_rust`.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context in which to create the typed array.
+    /// * `src` - The elements to copy into the new typed array.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the new typed array, or an error if `T` has no matching typed array type.
+    pub fn from_slice<T: Copy + 'static>(context: &Context<'a>, src: &[T]) -> Result<Self> {
+        let ty = TypedArrayType::for_rust::<T>()
+            .ok_or(Error::InvalidParameter("Unsupported typed array element type"))?;
+        let array = Self::new(context, ty, src.len())?;
+        array.copy_from_slice(src)?;
+        Ok(array)
+    }
+    
     /// Creates a new typed array from an existing buffer without copying.
     ///
     /// # Arguments
@@ -159,12 +215,17 @@ impl<'a> TypedArray<'a> {
     /// # Returns
     ///
     /// A Result containing the new typed array or an error.
-    pub fn from_bytes_no_copy(
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must point to `byte_length` writable bytes that stay valid until
+    /// `deallocator` is called, or for the lifetime of the context if there is none.
+    pub unsafe fn from_bytes_no_copy(
         context: &Context<'a>,
         ty: TypedArrayType,
         bytes: *mut c_void,
         byte_length: usize,
-        deallocator: Option<ffi::JSTypedArrayBytesDeallocator>,
+        deallocator: ffi::JSTypedArrayBytesDeallocator,
         deallocator_context: Option<*mut c_void>
     ) -> Result<Self> {
         let jsc_ty = ty.to_ffi();
@@ -186,7 +247,7 @@ impl<'a> TypedArray<'a> {
             }
             
             if raw.is_null() {
-                return Err(Error::JSError(format!("Failed to create typed array from bytes")));
+                return Err(Error::JSError("Failed to create typed array from bytes".to_string()));
             }
             
             Ok(TypedArray {
@@ -232,7 +293,7 @@ impl<'a> TypedArray<'a> {
             }
             
             if raw.is_null() {
-                return Err(Error::JSError(format!("Failed to create typed array from array buffer")));
+                return Err(Error::JSError("Failed to create typed array from array buffer".to_string()));
             }
             
             Ok(TypedArray {
@@ -284,7 +345,7 @@ impl<'a> TypedArray<'a> {
             }
             
             if raw.is_null() {
-                return Err(Error::JSError(format!("Failed to create typed array from array buffer with offset")));
+                return Err(Error::JSError("Failed to create typed array from array buffer with offset".to_string()));
             }
             
             Ok(TypedArray {
@@ -432,11 +493,13 @@ impl<'a> TypedArray<'a> {
         let context = self.object.context();
         
         let mut exception = ptr::null();
-        let ptr = ffi::JSObjectGetTypedArrayBytesPtr(
-            context.as_raw(),
-            self.object.as_raw(),
-            &mut exception
-        ) as *mut u8;
+        let ptr = unsafe {
+            ffi::JSObjectGetTypedArrayBytesPtr(
+                context.as_raw(),
+                self.object.as_raw(),
+                &mut exception
+            )
+        } as *mut u8;
         
         if !exception.is_null() {
             return Err(Error::from_js_exception(context.as_raw(), exception));
@@ -460,10 +523,10 @@ impl<'a> TypedArray<'a> {
     ///
     /// A Result containing a slice of the data buffer or an error.
     pub unsafe fn as_slice<T>(&self) -> Result<&[T]> {
-        let ptr = self.bytes_ptr()? as *const T;
+        let ptr = unsafe { self.bytes_ptr()? } as *const T;
         let len = self.length()?;
         
-        Ok(std::slice::from_raw_parts(ptr, len))
+        Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
     }
     
     /// Gets a mutable slice to the typed array's data buffer.
@@ -476,13 +539,216 @@ impl<'a> TypedArray<'a> {
     /// # Returns
     ///
     /// A Result containing a mutable slice of the data buffer or an error.
-    pub unsafe fn as_slice_mut<T>(&self) -> Result<&mut [T]> {
-        let ptr = self.bytes_ptr()? as *mut T;
+    pub unsafe fn as_slice_mut<T>(&mut self) -> Result<&mut [T]> {
+        let ptr = unsafe { self.bytes_ptr()? } as *mut T;
         let len = self.length()?;
         
-        Ok(std::slice::from_raw_parts_mut(ptr, len))
+        Ok(unsafe { std::slice::from_raw_parts_mut(ptr, len) })
+    }
+
+    /// Gets a slice of the typed array's elements after checking that `T` is the
+    /// element type of this array.
+    ///
+    /// # Safety
+    ///
+    /// The returned slice is only valid until the next time JavaScript code runs.
+    /// The caller must ensure the slice is not used after that.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the elements, or `Error::ConversionError` if `T` does not
+    /// match the array type.
+    unsafe fn checked_slice<T: 'static>(&self) -> Result<&[T]> {
+        let len = self.checked_length::<T>()?;
+        if len == 0 {
+            return Ok(&[]);
+        }
+        
+        let ptr = unsafe { self.bytes_ptr()? } as *const T;
+        Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
     }
     
+    /// Gets the elements of a `Int8Array` as a slice.
+    ///
+    /// Unlike `as_slice`, this checks the array type and returns `Error::ConversionError`
+    /// on a mismatch.
+    ///
+    /// # Safety
+    ///
+    /// The returned slice is only valid until the next time JavaScript code runs.
+    /// The caller must ensure the slice is not used after that.
+    pub unsafe fn as_i8_slice(&self) -> Result<&[i8]> {
+        unsafe { self.checked_slice() }
+    }
+    
+    /// Gets the elements of a `Uint8Array` or `Uint8ClampedArray` as a slice.
+    ///
+    /// Unlike `as_slice`, this checks the array type and returns `Error::ConversionError`
+    /// on a mismatch.
+    ///
+    /// # Safety
+    ///
+    /// The returned slice is only valid until the next time JavaScript code runs.
+    /// The caller must ensure the slice is not used after that.
+    pub unsafe fn as_u8_slice(&self) -> Result<&[u8]> {
+        unsafe { self.checked_slice() }
+    }
+    
+    /// Gets the elements of a `Int16Array` as a slice.
+    ///
+    /// Unlike `as_slice`, this checks the array type and returns `Error::ConversionError`
+    /// on a mismatch.
+    ///
+    /// # Safety
+    ///
+    /// The returned slice is only valid until the next time JavaScript code runs.
+    /// The caller must ensure the slice is not used after that.
+    pub unsafe fn as_i16_slice(&self) -> Result<&[i16]> {
+        unsafe { self.checked_slice() }
+    }
+    
+    /// Gets the elements of a `Uint16Array` as a slice.
+    ///
+    /// Unlike `as_slice`, this checks the array type and returns `Error::ConversionError`
+    /// on a mismatch.
+    ///
+    /// # Safety
+    ///
+    /// The returned slice is only valid until the next time JavaScript code runs.
+    /// The caller must ensure the slice is not used after that.
+    pub unsafe fn as_u16_slice(&self) -> Result<&[u16]> {
+        unsafe { self.checked_slice() }
+    }
+    
+    /// Gets the elements of a `Int32Array` as a slice.
+    ///
+    /// Unlike `as_slice`, this checks the array type and returns `Error::ConversionError`
+    /// on a mismatch.
+    ///
+    /// # Safety
+    ///
+    /// The returned slice is only valid until the next time JavaScript code runs.
+    /// The caller must ensure the slice is not used after that.
+    pub unsafe fn as_i32_slice(&self) -> Result<&[i32]> {
+        unsafe { self.checked_slice() }
+    }
+    
+    /// Gets the elements of a `Uint32Array` as a slice.
+    ///
+    /// Unlike `as_slice`, this checks the array type and returns `Error::ConversionError`
+    /// on a mismatch.
+    ///
+    /// # Safety
+    ///
+    /// The returned slice is only valid until the next time JavaScript code runs.
+    /// The caller must ensure the slice is not used after that.
+    pub unsafe fn as_u32_slice(&self) -> Result<&[u32]> {
+        unsafe { self.checked_slice() }
+    }
+    
+    /// Gets the elements of a `Float32Array` as a slice.
+    ///
+    /// Unlike `as_slice`, this checks the array type and returns `Error::ConversionError`
+    /// on a mismatch.
+    ///
+    /// # Safety
+    ///
+    /// The returned slice is only valid until the next time JavaScript code runs.
+    /// The caller must ensure the slice is not used after that.
+    pub unsafe fn as_f32_slice(&self) -> Result<&[f32]> {
+        unsafe { self.checked_slice() }
+    }
+    
+    /// Gets the elements of a `Float64Array` as a slice.
+    ///
+    /// Unlike `as_slice`, this checks the array type and returns `Error::ConversionError`
+    /// on a mismatch.
+    ///
+    /// # Safety
+    ///
+    /// The returned slice is only valid until the next time JavaScript code runs.
+    /// The caller must ensure the slice is not used after that.
+    pub unsafe fn as_f64_slice(&self) -> Result<&[f64]> {
+        unsafe { self.checked_slice() }
+    }
+    
+    /// Copies the elements of a slice into this typed array.
+    ///
+    /// The data pointer is only held for the duration of the copy, so no
+    /// reference into the JavaScript heap escapes this call.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The elements to copy. Its length must match the typed array length.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success, `Error::ConversionError` if `T` is not the element
+    /// type of this array, or an error if the length does not match.
+    pub fn copy_from_slice<T: Copy + 'static>(&self, src: &[T]) -> Result<()> {
+        let len = self.checked_length::<T>()?;
+
+        if src.len() != len {
+            return Err(Error::InvalidParameter("Source slice length does not match typed array length"));
+        }
+
+        if len == 0 {
+            return Ok(());
+        }
+
+        unsafe {
+            let dst = self.bytes_ptr()? as *mut T;
+            ptr::copy_nonoverlapping(src.as_ptr(), dst, len);
+        }
+
+        Ok(())
+    }
+
+    /// Copies the elements of this typed array into a new vector.
+    ///
+    /// The data pointer is only held for the duration of the copy, so no
+    /// reference into the JavaScript heap escapes this call.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the copied elements, or `Error::ConversionError` if `T` is
+    /// not the element type of this array.
+    pub fn to_vec<T: Copy + 'static>(&self) -> Result<Vec<T>> {
+        let len = self.checked_length::<T>()?;
+
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut out = Vec::with_capacity(len);
+
+        unsafe {
+            let src = self.bytes_ptr()? as *const T;
+            ptr::copy_nonoverlapping(src, out.as_mut_ptr(), len);
+            out.set_len(len);
+        }
+
+        Ok(out)
+    }
+
+    /// Returns the element count after checking that `T` is the element type of this
+    /// array. `u8` is accepted for both `Uint8Array` and `Uint8ClampedArray`.
+    fn checked_length<T: 'static>(&self) -> Result<usize> {
+        let matches = match TypedArrayType::for_rust::<T>() {
+            Some(TypedArrayType::Uint8Array) => matches!(self.ty, TypedArrayType::Uint8Array | TypedArrayType::Uint8ClampedArray),
+            ty => ty == Some(self.ty),
+        };
+        if !matches {
+            return Err(Error::ConversionError(format!(
+                "Cannot view {:?} elements as {}",
+                self.ty,
+                std::any::type_name::<T>()
+            )));
+        }
+
+        Ok(self.byte_length()? / self.ty.element_size())
+    }
+
     /// Gets the underlying ArrayBuffer for this typed array.
     ///
     /// # Returns
@@ -556,11 +822,16 @@ impl<'a> ArrayBuffer<'a> {
     /// # Returns
     ///
     /// A Result containing the new ArrayBuffer or an error.
-    pub fn from_bytes_no_copy(
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must point to `byte_length` writable bytes that stay valid until
+    /// `deallocator` is called, or for the lifetime of the context if there is none.
+    pub unsafe fn from_bytes_no_copy(
         context: &Context<'a>,
         bytes: *mut c_void,
         byte_length: usize,
-        deallocator: Option<ffi::JSTypedArrayBytesDeallocator>,
+        deallocator: ffi::JSTypedArrayBytesDeallocator,
         deallocator_context: Option<*mut c_void>
     ) -> Result<Self> {
         unsafe {
@@ -589,6 +860,67 @@ impl<'a> ArrayBuffer<'a> {
         }
     }
     
+    /// Creates a new ArrayBuffer that takes ownership of a vector's buffer.
+    ///
+    /// The bytes are not copied. The vector is dropped by JavaScriptCore when the
+    /// ArrayBuffer is garbage collected, or when creating it fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context in which to create the ArrayBuffer.
+    /// * `bytes` - The vector whose buffer becomes the ArrayBuffer's storage.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the new ArrayBuffer or an error.
+    pub fn from_vec(context: &Context<'a>, bytes: Vec<u8>) -> Result<Self> {
+        /// The parts needed to rebuild the vector in the deallocator.
+        struct VecParts {
+            length: usize,
+            capacity: usize,
+        }
+        
+        unsafe extern "C" fn deallocate(bytes: *mut c_void, deallocator_context: *mut c_void) {
+            unsafe {
+                let parts = Box::from_raw(deallocator_context as *mut VecParts);
+                drop(Vec::from_raw_parts(bytes as *mut u8, parts.length, parts.capacity));
+            }
+        }
+        
+        let mut bytes = mem::ManuallyDrop::new(bytes);
+        let length = bytes.len();
+        let parts = Box::into_raw(Box::new(VecParts { length, capacity: bytes.capacity() }));
+        
+        unsafe {
+            let mut exception = ptr::null();
+            let raw = ffi::JSObjectMakeArrayBufferWithBytesNoCopy(
+                context.as_raw(),
+                bytes.as_mut_ptr() as *mut c_void,
+                length,
+                Some(deallocate),
+                parts as *mut c_void,
+                &mut exception
+            );
+            
+            // JavaScriptCore owns the bytes once it has wrapped them in an ArrayBuffer,
+            // and calls `deallocate` when that is released, even if the call then
+            // throws. It only returns without taking ownership for a null context,
+            // which a Context never holds, so the bytes must not be freed here.
+            if !exception.is_null() {
+                return Err(Error::from_js_exception(context.as_raw(), exception));
+            }
+            
+            if raw.is_null() {
+                return Err(Error::JSError("Failed to create array buffer from vector".to_string()));
+            }
+            
+            let object = Object::from_raw(context.clone(), raw);
+            let typed_array = TypedArray { object, ty: TypedArrayType::ArrayBuffer };
+            
+            Ok(ArrayBuffer { typed_array })
+        }
+    }
+    
     /// Creates an ArrayBuffer from an existing JavaScript object.
     ///
     /// # Arguments
@@ -665,11 +997,13 @@ impl<'a> ArrayBuffer<'a> {
         let context = self.typed_array.object.context();
         
         let mut exception = ptr::null();
-        let ptr = ffi::JSObjectGetArrayBufferBytesPtr(
-            context.as_raw(),
-            self.typed_array.object.as_raw(),
-            &mut exception
-        ) as *mut u8;
+        let ptr = unsafe {
+            ffi::JSObjectGetArrayBufferBytesPtr(
+                context.as_raw(),
+                self.typed_array.object.as_raw(),
+                &mut exception
+            )
+        } as *mut u8;
         
         if !exception.is_null() {
             return Err(Error::from_js_exception(context.as_raw(), exception));
@@ -693,10 +1027,10 @@ impl<'a> ArrayBuffer<'a> {
     ///
     /// A Result containing a slice of the data or an error.
     pub unsafe fn as_slice(&self) -> Result<&[u8]> {
-        let ptr = self.bytes_ptr()?;
+        let ptr = unsafe { self.bytes_ptr()? };
         let len = self.byte_length()?;
         
-        Ok(std::slice::from_raw_parts(ptr, len))
+        Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
     }
     
     /// Gets a mutable slice to the ArrayBuffer's data.
@@ -709,11 +1043,11 @@ impl<'a> ArrayBuffer<'a> {
     /// # Returns
     ///
     /// A Result containing a mutable slice of the data or an error.
-    pub unsafe fn as_slice_mut(&self) -> Result<&mut [u8]> {
-        let ptr = self.bytes_ptr()?;
+    pub unsafe fn as_slice_mut(&mut self) -> Result<&mut [u8]> {
+        let ptr = unsafe { self.bytes_ptr()? };
         let len = self.byte_length()?;
         
-        Ok(std::slice::from_raw_parts_mut(ptr, len))
+        Ok(unsafe { std::slice::from_raw_parts_mut(ptr, len) })
     }
     
     /// Converts this ArrayBuffer to a JavaScript value.
@@ -724,4 +1058,34 @@ impl<'a> ArrayBuffer<'a> {
     pub fn to_value(&self) -> Value<'a> {
         self.typed_array.to_value()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn assert_element_type<T: 'static>(expected: TypedArrayType) {
+        assert_eq!(TypedArrayType::for_rust::<T>(), Some(expected));
+        assert_eq!(expected.element_size(), mem::size_of::<T>());
+    }
+    
+    #[test]
+    fn for_rust_matches_element_sizes() {
+        assert_element_type::<i8>(TypedArrayType::Int8Array);
+        assert_element_type::<u8>(TypedArrayType::Uint8Array);
+        assert_element_type::<i16>(TypedArrayType::Int16Array);
+        assert_element_type::<u16>(TypedArrayType::Uint16Array);
+        assert_element_type::<i32>(TypedArrayType::Int32Array);
+        assert_element_type::<u32>(TypedArrayType::Uint32Array);
+        assert_element_type::<f32>(TypedArrayType::Float32Array);
+        assert_element_type::<f64>(TypedArrayType::Float64Array);
+        assert_element_type::<i64>(TypedArrayType::BigInt64Array);
+        assert_element_type::<u64>(TypedArrayType::BigUint64Array);
+    }
+    
+    #[test]
+    fn for_rust_rejects_other_types() {
+        assert_eq!(TypedArrayType::for_rust::<bool>(), None);
+        assert_eq!(TypedArrayType::for_rust::<[u8; 4]>(), None);
+    }
+}
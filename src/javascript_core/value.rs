@@ -5,18 +5,18 @@
 //! JavaScriptCore C API. The Value struct represents any JavaScript value 
 //! (primitive or object), with methods for type checking, conversion, and creation.
 
-use std::marker::PhantomData;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ptr;
-use std::os::raw::c_double;
 
 use crate::javascript_core::context::Context;
-use crate::javascript_core::error::{Error, Result};
+use crate::javascript_core::exception::{Error, Result};
 use crate::javascript_core::ffi;
 use crate::javascript_core::object::Object;
 use crate::javascript_core::string::String;
 
 /// Represents the type of a JavaScript value.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ValueType {
     /// The unique undefined value.
     Undefined,
@@ -95,6 +95,33 @@ impl<'a> Value<'a> {
         ValueType::from_ffi(raw_type)
     }
     
+    /// Gets the result of the JavaScript `typeof` operator for this value.
+    ///
+    /// Unlike `get_type`, this distinguishes callable objects, which yield
+    /// "function". As in JavaScript, `null` yields "object".
+    ///
+    /// # Returns
+    ///
+    /// One of "undefined", "boolean", "number", "string", "symbol", "object" or
+    /// "function".
+    pub fn typeof_string(&self) -> &'static str {
+        match self.get_type() {
+            ValueType::Undefined => "undefined",
+            ValueType::Null => "object",
+            ValueType::Boolean => "boolean",
+            ValueType::Number => "number",
+            ValueType::String => "string",
+            ValueType::Symbol => "symbol",
+            ValueType::Object => {
+                if Object::from_value(self).is_ok_and(|object| object.is_function()) {
+                    "function"
+                } else {
+                    "object"
+                }
+            }
+        }
+    }
+    
     /// Creates an undefined value in the given context.
     ///
     /// # Arguments
@@ -205,6 +232,31 @@ impl<'a> Value<'a> {
         Value::from_raw(context, raw)
     }
     
+    /// Creates a standalone function value backed by a Rust closure.
+    ///
+    /// The function is not attached to any object, which makes it suitable for
+    /// passing as a callback to JavaScript APIs such as `setTimeout` or `Array.map`.
+    /// The closure is owned by the function object and dropped when the garbage
+    /// collector finalizes it, so it stays valid for as long as JavaScript can call it.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context in which to create the function.
+    /// * `callback` - The closure to invoke with the calling context and arguments.
+    ///
+    /// # Returns
+    ///
+    /// A new callable function value.
+    pub fn function<F>(context: &Context<'a>, callback: F) -> Self
+    where
+        F: for<'c> Fn(&Context<'c>, &[Value<'c>]) -> Result<Value<'c>> + 'static,
+    {
+        Object::function_with_callback(context, None, move |context, _function, _this, arguments| {
+            callback(context, arguments)
+        })
+        .to_value()
+    }
+    
     /// Creates a value from a JavaScript exception.
     ///
     /// # Arguments
@@ -300,6 +352,30 @@ impl<'a> Value<'a> {
         unsafe { ffi::JSValueIsDate(self.context.as_raw(), self.raw) }
     }
     
+    /// Checks if this value is a `Map`.
+    ///
+    /// This checks `instanceof` against the global `Map` constructor, so maps created
+    /// in other contexts are not detected.
+    ///
+    /// # Returns
+    ///
+    /// `true` if this value is a map, otherwise `false`.
+    pub fn is_map(&self) -> bool {
+        self.is_object() && self.is_instance_of_named("Map").unwrap_or(false)
+    }
+    
+    /// Checks if this value is a `Set`.
+    ///
+    /// This checks `instanceof` against the global `Set` constructor, so sets created
+    /// in other contexts are not detected.
+    ///
+    /// # Returns
+    ///
+    /// `true` if this value is a set, otherwise `false`.
+    pub fn is_set(&self) -> bool {
+        self.is_object() && self.is_instance_of_named("Set").unwrap_or(false)
+    }
+    
     /// Converts this value to a boolean.
     ///
     /// # Returns
@@ -329,6 +405,10 @@ impl<'a> Value<'a> {
     
     /// Converts this value to a string.
     ///
+    /// This applies the JavaScript ToString coercion, so any value converts: numbers
+    /// become their decimal form and objects typically become "[object Object]". Use
+    /// `as_string` to accept only values that are already strings.
+    ///
     /// # Returns
     ///
     /// A Result containing the string result of conversion, or an error if conversion fails.
@@ -349,6 +429,59 @@ impl<'a> Value<'a> {
         }
     }
     
+    /// Returns this value as a string without coercion.
+    ///
+    /// Unlike `to_string`, this fails for any value that is not a primitive string,
+    /// which makes it suitable for validating input from JavaScript.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the string, or `Error::InvalidType` if this value is not a string.
+    pub fn as_string(&self) -> Result<String> {
+        if !self.is_string() {
+            return Err(Error::InvalidType(format!("Expected a string, found {:?}", self.get_type())));
+        }
+        
+        self.to_string()
+    }
+    
+    /// Converts this value to a 64-bit float.
+    ///
+    /// This applies the JavaScript ToNumber coercion and is equivalent to `to_number`.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the numeric value, or an error if conversion throws.
+    pub fn as_f64(&self) -> Result<f64> {
+        self.to_number()
+    }
+    
+    /// Converts this value to a signed 64-bit integer.
+    ///
+    /// The value is converted with ToNumber and must then be a finite integer within
+    /// the safe integer range (±(2^53 - 1)), where every integer is represented exactly.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the integer, or `Error::ConversionError` if the number is
+    /// NaN, infinite, non-integral or outside the safe integer range.
+    pub fn as_i64(&self) -> Result<i64> {
+        safe_i64(self.to_number()?)
+    }
+    
+    /// Converts this value to an unsigned 64-bit integer.
+    ///
+    /// This has the same requirements as `as_i64` and additionally rejects negative
+    /// numbers.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the integer, or `Error::ConversionError` if the number
+    /// cannot be represented exactly as a u64.
+    pub fn as_u64(&self) -> Result<u64> {
+        safe_u64(self.to_number()?)
+    }
+    
     /// Converts this value to an object.
     ///
     /// # Returns
@@ -371,6 +504,71 @@ impl<'a> Value<'a> {
         }
     }
     
+    /// Makes this value usable from another context in the same context group.
+    ///
+    /// Using a value from a context in a different group is undefined behavior
+    /// in JavaScriptCore, so the groups are checked before the value is rebound.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context in which the value will be used.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the value bound to `context`, or an error if the contexts
+    /// do not share a context group.
+    pub fn transfer_to<'b>(&self, context: &Context<'b>) -> Result<Value<'b>> {
+        if !self.context.shares_group_with(context) {
+            return Err(Error::InvalidParameter("Contexts do not share a context group"));
+        }
+        
+        Ok(Value::from_raw(context, self.raw))
+    }
+    
+    /// Returns an iterator over the elements of this array.
+    ///
+    /// The array length is read once when the iterator is created, so elements
+    /// appended while iterating are not visited. Holes yield `undefined`.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the iterator, or an error if this value is not an array.
+    pub fn array_iter(&self) -> Result<ArrayIter<'a>> {
+        if !self.is_array() {
+            return Err(Error::InvalidType("Value is not an array".to_string()));
+        }
+        
+        let object = Object::from_value(self)?;
+        let length = object.get_property("length")?.to_number()? as u32;
+        
+        Ok(ArrayIter {
+            object,
+            index: 0,
+            length,
+        })
+    }
+    
+    /// Collects the elements of this array into a vector.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the elements, or an error if this value is not an array.
+    pub fn to_vec(&self) -> Result<Vec<Value<'a>>> {
+        self.array_iter()?.collect()
+    }
+    
+    /// Converts this value into an object, consuming the value.
+    ///
+    /// Unlike `to_object`, this does not box primitive values; it fails if the
+    /// value is not already an object.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the object, or an error if the value is not an object.
+    pub fn into_object(self) -> Result<Object<'a>> {
+        Object::from_value(&self)
+    }
+    
     /// Creates a JavaScript value from a JSON string.
     ///
     /// # Arguments
@@ -425,6 +623,60 @@ impl<'a> Value<'a> {
         }
     }
     
+    /// Converts a value to a JSON string by calling the global `JSON.stringify`.
+    ///
+    /// Unlike `to_json`, this accepts a replacer function, which can be used to omit
+    /// or transform values, for example to drop circular references.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context whose `JSON` object is used.
+    /// * `value` - The value to convert.
+    /// * `replacer` - An optional replacer function or array of property names.
+    /// * `indent` - The number of spaces to indent when nesting. If 0, the resulting JSON will not contain newlines.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the JSON string, or an error if the value cannot be converted.
+    pub fn stringify(context: &Context<'a>, value: &Value<'a>, replacer: Option<&Object<'a>>, indent: u32) -> Result<String> {
+        let json = Object::from_value(&context.get_global("JSON")?)?;
+        let replacer = replacer.map_or_else(|| Value::null(context), |replacer| replacer.to_value());
+        let result = json.invoke(
+            "stringify",
+            &[value.clone(), replacer, Value::number(context, indent as f64)],
+        )?;
+        
+        if result.is_undefined() {
+            return Err(Error::JSError("Failed to convert value to JSON".to_string()));
+        }
+        
+        result.to_string()
+    }
+    
+    /// Parses a JSON string by calling the global `JSON.parse`.
+    ///
+    /// Unlike `from_json`, this accepts a reviver function that can transform each
+    /// parsed value.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context whose `JSON` object is used.
+    /// * `text` - A string containing valid JSON.
+    /// * `reviver` - An optional reviver function.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the parsed value, or an error if parsing or the reviver throws.
+    pub fn parse(context: &Context<'a>, text: &str, reviver: Option<&Object<'a>>) -> Result<Self> {
+        let json = Object::from_value(&context.get_global("JSON")?)?;
+        let mut arguments = vec![Value::string(context, text)];
+        if let Some(reviver) = reviver {
+            arguments.push(reviver.to_value());
+        }
+        
+        json.invoke("parse", &arguments)
+    }
+    
     /// Compares this value with another for equality using the JavaScript == operator.
     ///
     /// # Arguments
@@ -471,6 +723,47 @@ impl<'a> Value<'a> {
         }
     }
     
+    /// Compares this value with another using the JavaScript == operator.
+    ///
+    /// This is an alias of `equals`, named to distinguish abstract equality (which
+    /// performs type coercion and may run user code) from `strict_equals` and
+    /// `same_value`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The value to compare with.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `true` if the values are loosely equal, `false` otherwise, or an error if comparison fails.
+    pub fn loose_eq(&self, other: &Value<'a>) -> Result<bool> {
+        self.equals(other)
+    }
+    
+    /// Compares this value with another using the SameValue algorithm, as `Object.is` does.
+    ///
+    /// This differs from `strict_equals` only for numbers: `NaN` is the same value as
+    /// `NaN`, while `+0` and `-0` are different values.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The value to compare with.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the values are the same value, `false` otherwise.
+    pub fn same_value(&self, other: &Value<'a>) -> bool {
+        if self.is_number() && other.is_number() {
+            return match (self.to_number(), other.to_number()) {
+                (Ok(a), Ok(b)) if a.is_nan() && b.is_nan() => true,
+                (Ok(a), Ok(b)) => a.to_bits() == b.to_bits(),
+                _ => false,
+            };
+        }
+        
+        self.strict_equals(other)
+    }
+    
     /// Checks if this value is an instance of a constructor using the JavaScript instanceof operator.
     ///
     /// # Arguments
@@ -498,6 +791,31 @@ impl<'a> Value<'a> {
         }
     }
     
+    /// Checks if this value is an instance of a named global constructor.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of a global constructor, such as "Array" or "Date".
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `true` if this value is an instance of the constructor, or `false`
+    /// if it is not or no such global constructor exists.
+    pub fn is_instance_of_named(&self, name: &str) -> Result<bool> {
+        let constructor = self.context.global_object().get_property(name)?;
+        
+        if !constructor.is_object() {
+            return Ok(false);
+        }
+        
+        let constructor = Object::from_value(&constructor)?;
+        if !constructor.is_constructor() {
+            return Ok(false);
+        }
+        
+        self.is_instance_of(&constructor)
+    }
+    
     /// Protects this value from garbage collection.
     ///
     /// A value may be protected multiple times and must be unprotected an equal number of times
@@ -518,6 +836,25 @@ impl<'a> Value<'a> {
         }
     }
     
+    /// Creates a weak reference to this value.
+    ///
+    /// Unlike protecting the value, a weak reference does not keep it alive. Only
+    /// objects can be referenced weakly.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the WeakValue, or `Error::InvalidType` if this value is
+    /// not an object.
+    pub fn downgrade(&self) -> Result<WeakValue<'a>> {
+        if !self.is_object() {
+            return Err(Error::InvalidType(format!("Only objects can be weakly referenced, found {:?}", self.get_type())));
+        }
+        
+        let constructor = self.context.global_object().get_property("WeakRef")?.into_object()?;
+        let weak_ref = constructor.construct(std::slice::from_ref(self))?;
+        Ok(WeakValue { weak_ref: ProtectedValue::new(weak_ref.to_value()) })
+    }
+    
     /// Determines if this value is of a specific object class.
     ///
     /// # Arguments
@@ -527,7 +864,11 @@ impl<'a> Value<'a> {
     /// # Returns
     ///
     /// `true` if this value is an object of the specified class, `false` otherwise.
-    pub fn is_of_class(&self, class: ffi::JSClassRef) -> bool {
+    ///
+    /// # Safety
+    ///
+    /// `class` must be a valid JSClassRef.
+    pub unsafe fn is_of_class(&self, class: ffi::JSClassRef) -> bool {
         unsafe {
             ffi::JSValueIsObjectOfClass(self.context.as_raw(), self.raw, class)
         }
@@ -568,14 +909,333 @@ impl<'a> Clone for Value<'a> {
     }
 }
 
+impl<'a> fmt::Debug for Value<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Objects longer than this are shown as [object] rather than JSON
+        const MAX_JSON_LEN: usize = 128;
+        
+        match self.get_type() {
+            ValueType::Undefined => write!(f, "Undefined"),
+            ValueType::Null => write!(f, "Null"),
+            ValueType::Boolean => write!(f, "Boolean({})", self.to_boolean()),
+            ValueType::Number => match self.to_number() {
+                Ok(n) => write!(f, "Number({})", n),
+                Err(_) => write!(f, "Number(<error>)"),
+            },
+            ValueType::String => match self.to_string() {
+                Ok(s) => write!(f, "String({:?})", s.to_string()),
+                Err(_) => write!(f, "String(<error>)"),
+            },
+            ValueType::Symbol => write!(f, "Symbol"),
+            ValueType::Object => {
+                if Object::from_value(self).map(|obj| obj.is_function()).unwrap_or(false) {
+                    return write!(f, "Object([function])");
+                }
+                
+                match self.to_json(0) {
+                    Ok(json) => {
+                        let json = json.to_string();
+                        if json.len() <= MAX_JSON_LEN {
+                            write!(f, "Object({})", json)
+                        } else {
+                            write!(f, "Object([object])")
+                        }
+                    },
+                    Err(_) => write!(f, "Object(<error>)"),
+                }
+            },
+        }
+    }
+}
+
+/// Values compare with the JavaScript === operator (`strict_equals`), so `NaN` is
+/// not equal to itself and objects are compared by identity. Use `loose_eq` for ==
+/// and `same_value` for `Object.is` semantics.
 impl<'a> PartialEq for Value<'a> {
     fn eq(&self, other: &Self) -> bool {
         self.strict_equals(other)
     }
 }
 
+/// An iterator over the elements of a JavaScript array.
+///
+/// Created by `Value::array_iter`.
+pub struct ArrayIter<'a> {
+    object: Object<'a>,
+    index: u32,
+    length: u32,
+}
+
+impl<'a> Iterator for ArrayIter<'a> {
+    type Item = Result<Value<'a>>;
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.length {
+            return None;
+        }
+        
+        let item = self.object.get_property_at_index(self.index);
+        self.index += 1;
+        Some(item)
+    }
+    
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.length - self.index) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for ArrayIter<'a> {}
+
+/// A wrapper that makes a Value usable as a `HashMap` or `HashSet` key.
+///
+/// Equality follows the SameValueZero algorithm used for JavaScript `Map` keys,
+/// so objects are compared by identity rather than structure: two distinct
+/// objects with the same properties are different keys, while two references to
+/// the same object are the same key. Primitives hash by their content. Unlike the
+/// === operator, `NaN` equals itself, which `Eq` requires; `0` and `-0` are
+/// still the same key.
+#[derive(Clone)]
+pub struct HashableValue<'a>(pub Value<'a>);
+
+impl<'a> HashableValue<'a> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> Value<'a> {
+        self.0
+    }
+}
+
+impl<'a> PartialEq for HashableValue<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.0.is_number()
+            && other.0.is_number()
+            && let (Ok(a), Ok(b)) = (self.0.to_number(), other.0.to_number())
+        {
+            return number_key(a) == number_key(b);
+        }
+        self.0.strict_equals(&other.0)
+    }
+}
+
+impl<'a> Eq for HashableValue<'a> {}
+
+/// Maps a number to the bits that identify it as a SameValueZero key.
+///
+/// Every `NaN` maps to the same bits, and so do `0` and `-0`.
+fn number_key(n: f64) -> u64 {
+    if n.is_nan() {
+        f64::NAN.to_bits()
+    } else if n == 0.0 {
+        0
+    } else {
+        n.to_bits()
+    }
+}
+
+impl<'a> Hash for HashableValue<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let ty = self.0.get_type();
+        ty.hash(state);
+        
+        match ty {
+            ValueType::Object | ValueType::Symbol => (self.0.raw as usize).hash(state),
+            ValueType::Number => {
+                number_key(self.0.to_number().unwrap_or(f64::NAN)).hash(state);
+            }
+            ValueType::String => {
+                if let Ok(s) = self.0.to_string() {
+                    s.to_string().hash(state);
+                }
+            }
+            ValueType::Boolean => self.0.to_boolean().hash(state),
+            ValueType::Undefined | ValueType::Null => {}
+        }
+    }
+}
+
+impl<'a> From<Value<'a>> for HashableValue<'a> {
+    fn from(value: Value<'a>) -> Self {
+        HashableValue(value)
+    }
+}
+
+/// A Value protected from garbage collection for as long as this wrapper lives.
+///
+/// The value is protected once on construction and unprotected exactly once on
+/// drop, so protection can't become unbalanced. Use this to keep a JavaScript value
+/// (such as a callback or promise resolver) alive across garbage collection cycles
+/// when it is stored outside the stack.
+///
+/// Like Value, a ProtectedValue is tied to its context and is neither `Send` nor
+/// `Sync`: it must be used and dropped on the thread that owns the context.
+pub struct ProtectedValue<'a> {
+    value: Value<'a>,
+}
+
+impl<'a> ProtectedValue<'a> {
+    /// Protects a value from garbage collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to protect.
+    ///
+    /// # Returns
+    ///
+    /// A ProtectedValue that unprotects the value when dropped.
+    pub fn new(value: Value<'a>) -> Self {
+        value.protect();
+        ProtectedValue { value }
+    }
+    
+    /// Returns a reference to the protected value.
+    pub fn get(&self) -> &Value<'a> {
+        &self.value
+    }
+}
+
+impl<'a> Drop for ProtectedValue<'a> {
+    fn drop(&mut self) {
+        self.value.unprotect();
+    }
+}
+
+impl<'a> fmt::Debug for ProtectedValue<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ProtectedValue").field(&self.value).finish()
+    }
+}
+
+/// A weak reference to a JavaScript object, created with `Value::downgrade`.
+///
+/// This is implemented with a JavaScript `WeakRef`: the WeakRef object itself is
+/// protected from garbage collection, while the object it refers to is not, so the
+/// garbage collector may reclaim the target once nothing else references it. As
+/// with any WeakRef, a target that was accessed during the current job stays alive
+/// at least until that job completes.
+pub struct WeakValue<'a> {
+    weak_ref: ProtectedValue<'a>,
+}
+
+impl<'a> WeakValue<'a> {
+    /// Returns the referenced value if it has not been garbage collected.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context in which to return the value.
+    ///
+    /// # Returns
+    ///
+    /// The referenced value, or `None` if it has been collected.
+    pub fn upgrade(&self, context: &Context<'a>) -> Option<Value<'a>> {
+        let weak_ref = self.weak_ref.get().to_object().ok()?;
+        let deref = weak_ref.get_property("deref").ok()?.into_object().ok()?;
+        let target = deref.call(Some(&weak_ref), &[]).ok()?;
+        if target.is_undefined() {
+            return None;
+        }
+        
+        Some(Value::from_raw(context, target.as_raw()))
+    }
+}
+
+impl<'a> fmt::Debug for WeakValue<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakValue").finish_non_exhaustive()
+    }
+}
+
 impl<'a> From<Object<'a>> for Value<'a> {
     fn from(obj: Object<'a>) -> Self {
         obj.to_value()
     }
-}
\ No newline at end of file
+}
+
+impl<'a> TryFrom<&Value<'a>> for f64 {
+    type Error = Error;
+    
+    fn try_from(value: &Value<'a>) -> Result<Self> {
+        if !value.is_number() {
+            return Err(Error::InvalidType(format!("Expected a number, found {:?}", value.get_type())));
+        }
+        
+        value.to_number()
+    }
+}
+
+impl<'a> TryFrom<&Value<'a>> for bool {
+    type Error = Error;
+    
+    fn try_from(value: &Value<'a>) -> Result<Self> {
+        if !value.is_boolean() {
+            return Err(Error::InvalidType(format!("Expected a boolean, found {:?}", value.get_type())));
+        }
+        
+        Ok(value.to_boolean())
+    }
+}
+
+impl<'a> TryFrom<&Value<'a>> for std::string::String {
+    type Error = Error;
+    
+    fn try_from(value: &Value<'a>) -> Result<Self> {
+        Ok(value.as_string()?.to_string())
+    }
+}
+
+/// Converts a number to an i64 if it is an exactly representable integer.
+fn safe_i64(number: f64) -> Result<i64> {
+    const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+    
+    if !number.is_finite() {
+        return Err(Error::ConversionError(format!("{} is not finite", number)));
+    }
+    if number.fract() != 0.0 {
+        return Err(Error::ConversionError(format!("{} is not an integer", number)));
+    }
+    if number.abs() > MAX_SAFE_INTEGER {
+        return Err(Error::ConversionError(format!("{} is outside the safe integer range", number)));
+    }
+    
+    Ok(number as i64)
+}
+
+/// Converts a number to a u64 if it is an exactly representable, non-negative integer.
+fn safe_u64(number: f64) -> Result<u64> {
+    let integer = safe_i64(number)?;
+    u64::try_from(integer).map_err(|_| Error::ConversionError(format!("{} is negative", number)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_key_follows_same_value_zero() {
+        assert_eq!(number_key(f64::NAN), number_key(-f64::NAN));
+        assert_eq!(number_key(f64::NAN), number_key(f64::from_bits(0x7ff8_0000_0000_0001)));
+        assert_eq!(number_key(0.0), number_key(-0.0));
+        assert_ne!(number_key(1.0), number_key(-1.0));
+        assert_ne!(number_key(f64::NAN), number_key(f64::INFINITY));
+    }
+
+    #[test]
+    fn safe_integers_convert_exactly() {
+        assert_eq!(safe_i64(42.0).unwrap(), 42);
+        assert_eq!(safe_i64(-9007199254740991.0).unwrap(), -9007199254740991);
+        assert_eq!(safe_u64(9007199254740991.0).unwrap(), 9007199254740991);
+        assert_eq!(safe_u64(-0.0).unwrap(), 0);
+    }
+
+    #[test]
+    fn unsafe_integers_are_rejected() {
+        // 9007199254740993 can't be represented, so it reads as 9007199254740992
+        let imprecise: f64 = "9007199254740993".parse().unwrap();
+        assert!(matches!(safe_i64(imprecise), Err(Error::ConversionError(_))));
+        assert!(matches!(safe_u64(imprecise), Err(Error::ConversionError(_))));
+        assert!(matches!(safe_i64(3.5), Err(Error::ConversionError(_))));
+        assert!(matches!(safe_u64(3.5), Err(Error::ConversionError(_))));
+        assert!(matches!(safe_i64(f64::NAN), Err(Error::ConversionError(_))));
+        assert!(matches!(safe_i64(f64::INFINITY), Err(Error::ConversionError(_))));
+        assert!(matches!(safe_u64(-1.0), Err(Error::ConversionError(_))));
+    }
+}
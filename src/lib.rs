@@ -1,4 +1,6 @@
+#[cfg(feature = "app")]
 pub mod app_core;
+pub mod javascript_core;
 pub mod ul;
 
 pub use ul::*;
\ No newline at end of file
@@ -5,16 +5,25 @@
 //! Ultralight is a lightweight, cross-platform browser engine designed for embedding in applications.
 //! These bindings provide a safe, idiomatic Rust interface to the Ultralight C API.
 //!
+//! ## Threading
+//!
+//! Ultralight is single-threaded: the renderer, its views and their surfaces must
+//! all be used from the thread that created the renderer. Wrappers tied to that
+//! thread, such as [`View`] and [`Surface`], are neither `Send` nor `Sync`.
+//!
 //! ## Example usage
 //!
-//! ```rust
-//! use ultralight_rs::*;
+//! ```no_run
+//! use ul::*;
 //!
 //! fn main() {
-//!     // Initialize the platform with default handlers
-//!     Platform::enable_default_logger();
-//!     Platform::enable_platform_file_system("./resources/");
-//!     Platform::enable_platform_font_loader();
+//!     // Initialize the platform with the default handlers of the `app` feature
+//!     # #[cfg(feature = "app")]
+//!     # {
+//!     ul::app_core::platform::enable_default_logger("./ultralight.log");
+//!     ul::app_core::platform::enable_platform_file_system("./resources/");
+//!     ul::app_core::platform::enable_platform_font_loader();
+//!     # }
 //!     
 //!     // Create configuration
 //!     let config = Config::new();
@@ -52,14 +61,19 @@
 
 pub mod bitmap;
 pub mod buffer;
+pub mod clipboard;
 pub mod config;
 pub mod error;
 pub mod events;
 pub mod ffi;
+pub mod font_loader;
 pub mod geometry;
+pub mod gpu_driver;
 pub mod image_source;
+pub mod logger;
 pub mod platform;
 pub mod renderer;
+pub mod screenshot;
 pub mod session;
 pub mod string;
 pub mod surface;
@@ -67,23 +81,33 @@ pub mod view;
 pub mod view_config;
 
 // Re-exports
-pub use bitmap::{Bitmap, BitmapFormat};
+pub use bitmap::{AlphaExpansion, Bitmap, BitmapFormat};
 pub use buffer::Buffer;
-pub use config::Config;
+pub use clipboard::Clipboard;
+pub use config::{Config, ConfigBuilder};
 pub use error::Error;
 pub use events::{
     GamepadAxisEvent, GamepadButtonEvent, GamepadEvent, GamepadEventType, KeyEvent, KeyEventType,
     MouseButton, MouseEvent, MouseEventType, ScrollEvent, ScrollEventType,
 };
+pub use font_loader::{FontFile, FontLoader};
 pub use geometry::{IntRect, Rect};
+pub use gpu_driver::{
+    Command, CommandType, GpuDriver, GpuState, IndexBuffer, RenderBuffer, ShaderType,
+    VertexBuffer, VertexBufferFormat,
+};
 pub use image_source::ImageSource;
+pub use logger::{LogLevel, Logger};
 pub use platform::Platform;
-pub use renderer::Renderer;
+pub use renderer::{MemoryUsage, MemoryUsageEntry, Renderer};
+pub use screenshot::{render_html_to_bitmap, render_html_to_png, render_url_to_png};
 pub use session::Session;
 pub use string::String;
-pub use surface::{BitmapSurface, Surface, SurfaceDefinition};
-pub use view::View;
-pub use view_config::ViewConfig;
+pub use surface::{
+    BitmapSurface, CustomSurface, Image, ResizeInfo, Surface, SurfaceDefinition, VecSurface,
+};
+pub use view::{ConsoleMessage, LoadState, View};
+pub use view_config::{ViewConfig, ViewConfigBuilder};
 
 // Constants and enums
 pub use ffi::{
@@ -11,6 +11,15 @@ use std::slice;
 
 pub use crate::ul::ffi::ULBitmapFormat as BitmapFormat;
 
+/// How A8 pixels are expanded when converting to BGRA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlphaExpansion {
+    /// Copy the alpha value into every channel (premultiplied white).
+    AllChannels,
+    /// Use white color channels with the A8 value as alpha only.
+    WhiteColor,
+}
+
 /// A safe wrapper around Ultralight's ULBitmap type.
 pub struct Bitmap {
     raw: ULBitmap,
@@ -189,6 +198,168 @@ impl Bitmap {
     pub fn swap_red_blue_channels(&self) {
         unsafe { ulBitmapSwapRedBlueChannels(self.raw) }
     }
+
+    /// Read a single pixel as RGBA.
+    ///
+    /// A8 pixels are returned as `[a, a, a, a]`, matching how `convert_to` expands
+    /// them. Returns `None` if the coordinates are out of range or the pixels cannot
+    /// be locked. This locks the bitmap on every call, so prefer `lock_pixels` when
+    /// reading many pixels.
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        let offset = self.pixel_offset(x, y)?;
+        let pixels = self.lock_pixels().ok()?;
+        let data = pixels.as_slice();
+
+        match self.format() {
+            BitmapFormat::kBitmapFormat_A8_UNORM => {
+                let a = *data.get(offset)?;
+                Some([a, a, a, a])
+            }
+            BitmapFormat::kBitmapFormat_BGRA8_UNORM_SRGB => {
+                let bgra = data.get(offset..offset + 4)?;
+                Some([bgra[2], bgra[1], bgra[0], bgra[3]])
+            }
+        }
+    }
+
+    /// Write a single pixel from RGBA.
+    ///
+    /// A8 bitmaps only store the alpha component. Like `get_pixel`, this locks the
+    /// bitmap on every call.
+    pub fn set_pixel(&self, x: u32, y: u32, rgba: [u8; 4]) -> Result<(), Error> {
+        let out_of_range = || Error::InvalidArgument("Pixel coordinates out of range");
+        let offset = self.pixel_offset(x, y).ok_or_else(out_of_range)?;
+        let mut pixels = self.lock_pixels()?;
+        let data = pixels.as_slice_mut();
+
+        match self.format() {
+            BitmapFormat::kBitmapFormat_A8_UNORM => {
+                *data.get_mut(offset).ok_or_else(out_of_range)? = rgba[3];
+            }
+            BitmapFormat::kBitmapFormat_BGRA8_UNORM_SRGB => {
+                data.get_mut(offset..offset + 4)
+                    .ok_or_else(out_of_range)?
+                    .copy_from_slice(&[rgba[2], rgba[1], rgba[0], rgba[3]]);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fill every pixel with a color.
+    ///
+    /// A8 bitmaps are filled with the alpha component only. Padding bytes at the end
+    /// of each row are left untouched.
+    pub fn fill(&self, r: u8, g: u8, b: u8, a: u8) -> Result<(), Error> {
+        let pattern: &[u8] = match self.format() {
+            BitmapFormat::kBitmapFormat_A8_UNORM => &[a],
+            BitmapFormat::kBitmapFormat_BGRA8_UNORM_SRGB => &[b, g, r, a],
+        };
+        let row_len = self.width() as usize * pattern.len();
+        if row_len == 0 {
+            return Ok(());
+        }
+        let mut pixels = self.lock_pixels()?;
+
+        for row in pixels.as_slice_mut().chunks_mut(self.row_bytes() as usize) {
+            let Some(row) = row.get_mut(..row_len) else {
+                break;
+            };
+            for pixel in row.chunks_exact_mut(pattern.len()) {
+                pixel.copy_from_slice(pattern);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the byte offset of a pixel, or `None` if it is out of range.
+    fn pixel_offset(&self, x: u32, y: u32) -> Option<usize> {
+        if x >= self.width() || y >= self.height() {
+            return None;
+        }
+
+        Some(y as usize * self.row_bytes() as usize + x as usize * self.bpp() as usize)
+    }
+
+    /// Convert the bitmap into a new bitmap with the specified format.
+    ///
+    /// A8 pixels expand to white BGRA pixels using the A8 value as alpha
+    /// (premultiplied, so each color channel equals alpha). BGRA pixels reduce to A8
+    /// by taking the alpha channel. Converting to the same format returns a copy.
+    pub fn convert_to(&self, format: BitmapFormat) -> Result<Bitmap, Error> {
+        match (self.format(), format) {
+            (source, target) if source == target => Ok(Self::from_copy(self)),
+            (
+                BitmapFormat::kBitmapFormat_A8_UNORM,
+                BitmapFormat::kBitmapFormat_BGRA8_UNORM_SRGB,
+            ) => self.to_bgra(AlphaExpansion::AllChannels),
+            (
+                BitmapFormat::kBitmapFormat_BGRA8_UNORM_SRGB,
+                BitmapFormat::kBitmapFormat_A8_UNORM,
+            ) => self.convert_pixels(format, 4, 1, |src, dst| dst[0] = src[3]),
+            _ => Err(Error::InvalidArgument(
+                "Unsupported bitmap format conversion",
+            )),
+        }
+    }
+
+    /// Expand an A8 bitmap into a new BGRA bitmap.
+    ///
+    /// A bitmap that is already BGRA is copied unchanged.
+    pub fn to_bgra(&self, expansion: AlphaExpansion) -> Result<Bitmap, Error> {
+        if self.format() == BitmapFormat::kBitmapFormat_BGRA8_UNORM_SRGB {
+            return Ok(Self::from_copy(self));
+        }
+
+        self.convert_pixels(
+            BitmapFormat::kBitmapFormat_BGRA8_UNORM_SRGB,
+            1,
+            4,
+            |src, dst| match expansion {
+                AlphaExpansion::AllChannels => dst.fill(src[0]),
+                AlphaExpansion::WhiteColor => dst.copy_from_slice(&[0xFF, 0xFF, 0xFF, src[0]]),
+            },
+        )
+    }
+
+    /// Allocate a bitmap of the target format and convert each pixel, honoring row padding.
+    fn convert_pixels(
+        &self,
+        format: BitmapFormat,
+        src_bpp: usize,
+        dst_bpp: usize,
+        convert: impl Fn(&[u8], &mut [u8]),
+    ) -> Result<Bitmap, Error> {
+        let width = self.width() as usize;
+        let height = self.height() as usize;
+        let converted = Self::new(self.width(), self.height(), format);
+        if width == 0 || height == 0 {
+            return Ok(converted);
+        }
+
+        let src_row_bytes = self.row_bytes() as usize;
+        let dst_row_bytes = converted.row_bytes() as usize;
+        let src = self.lock_pixels()?;
+        let mut dst = converted.lock_pixels()?;
+        let src_pixels = src.as_slice();
+        let dst_pixels = dst.as_slice_mut();
+
+        for y in 0..height {
+            let src_row = &src_pixels[y * src_row_bytes..][..width * src_bpp];
+            let dst_row = &mut dst_pixels[y * dst_row_bytes..][..width * dst_bpp];
+            for (src_pixel, dst_pixel) in src_row
+                .chunks_exact(src_bpp)
+                .zip(dst_row.chunks_exact_mut(dst_bpp))
+            {
+                convert(src_pixel, dst_pixel);
+            }
+        }
+
+        drop(dst);
+        drop(src);
+        Ok(converted)
+    }
 }
 
 impl Clone for Bitmap {
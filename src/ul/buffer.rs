@@ -14,6 +14,12 @@ pub struct Buffer {
 
 extern "C" fn noop_buffer_destroy_callback(_: *mut c_void, _: *mut c_void) {}
 
+extern "C" fn vec_buffer_destroy_callback(user_data: *mut c_void, _: *mut c_void) {
+    unsafe {
+        drop(Box::from_raw(user_data as *mut Vec<u8>));
+    }
+}
+
 impl Buffer {
     /// Create a new buffer from a raw ULBuffer pointer.
     ///
@@ -39,6 +45,24 @@ impl Buffer {
         }
     }
 
+    /// Create a new buffer that takes ownership of the given data without copying it.
+    ///
+    /// The Vec is released when Ultralight destroys the buffer.
+    pub fn from_vec(data: Vec<u8>) -> Self {
+        let mut data = Box::new(data);
+        let ptr = data.as_mut_ptr();
+        let len = data.len();
+        unsafe {
+            let raw = ulCreateBuffer(
+                ptr as *mut c_void,
+                len,
+                Box::into_raw(data) as *mut c_void,
+                vec_buffer_destroy_callback,
+            );
+            Self { raw, owned: true }
+        }
+    }
+
     /// Create a new buffer with a copy of the given data.
     pub fn from_copy(data: &[u8]) -> Self {
         unsafe {
@@ -52,6 +76,14 @@ impl Buffer {
         self.raw
     }
 
+    /// Release ownership of the raw ULBuffer, e.g. to return it to Ultralight.
+    ///
+    /// The buffer will no longer be destroyed when this wrapper is dropped.
+    pub fn into_raw(mut self) -> ULBuffer {
+        self.owned = false;
+        self.raw
+    }
+
     /// Get a slice of the buffer data.
     pub fn as_slice(&self) -> &[u8] {
         unsafe {
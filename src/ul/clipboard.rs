@@ -0,0 +1,64 @@
+use crate::ul::ffi::{ULClipboard, ULString};
+use crate::ul::string::String;
+use std::cell::RefCell;
+
+/// A clipboard implementation that Ultralight uses for copy and paste.
+///
+/// Implement this to connect Ultralight to the operating system clipboard.
+pub trait Clipboard {
+    /// Clear the clipboard.
+    fn clear(&self);
+
+    /// Read plain text from the clipboard.
+    fn read_text(&self) -> std::string::String;
+
+    /// Write plain text to the clipboard.
+    fn write_text(&self, text: &str);
+}
+
+thread_local! {
+    static ACTIVE_CLIPBOARD: RefCell<Option<Box<dyn Clipboard>>> = RefCell::new(None);
+}
+
+/// Install a clipboard as the active clipboard for the current thread.
+pub(crate) fn set_active_clipboard<C: Clipboard + 'static>(clipboard: C) {
+    ACTIVE_CLIPBOARD.with(|cell| {
+        *cell.borrow_mut() = Some(Box::new(clipboard));
+    });
+}
+
+fn with_clipboard<R: Default>(f: impl FnOnce(&dyn Clipboard) -> R) -> R {
+    ACTIVE_CLIPBOARD.with(|cell| match cell.borrow().as_ref() {
+        Some(clipboard) => f(clipboard.as_ref()),
+        None => R::default(),
+    })
+}
+
+// Trampoline functions for the callbacks
+extern "C" fn clear_trampoline() {
+    with_clipboard(|c| c.clear());
+}
+
+// Ultralight passes in a string it owns and expects the text to be assigned to it,
+// so the result is copied into `result` rather than returned.
+extern "C" fn read_plain_text_trampoline(result: ULString) {
+    let text = with_clipboard(|c| c.read_text());
+    let mut result = unsafe { String::from_raw(result, false) };
+    let _ = result.assign(&String::from_utf8(text.as_bytes()));
+}
+
+extern "C" fn write_plain_text_trampoline(text: ULString) {
+    let text = unsafe { String::from_raw(text, false) };
+    if let Ok(text) = text.as_str() {
+        with_clipboard(|c| c.write_text(text));
+    }
+}
+
+/// Build a raw ULClipboard that dispatches to the active clipboard for the current thread.
+pub(crate) fn raw_clipboard() -> ULClipboard {
+    ULClipboard {
+        clear: clear_trampoline,
+        read_plain_text: read_plain_text_trampoline,
+        write_plain_text: write_plain_text_trampoline,
+    }
+}
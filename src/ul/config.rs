@@ -1,4 +1,5 @@
 use crate::ul::String;
+use crate::ul::error::Error;
 use crate::ul::ffi::{
     ULConfig, ULFaceWinding, ULFontHinting, ulConfigSetAnimationTimerDelay,
     ulConfigSetBitmapAlignment, ulConfigSetCachePath, ulConfigSetFaceWinding, ulConfigSetFontGamma,
@@ -8,10 +9,17 @@ use crate::ul::ffi::{
     ulConfigSetRecycleDelay, ulConfigSetResourcePathPrefix, ulConfigSetScrollTimerDelay,
     ulConfigSetUserStylesheet, ulCreateConfig, ulDestroyConfig,
 };
+use std::fs;
+use std::path::Path;
+
+/// The resource path prefix Ultralight uses when none is set.
+const DEFAULT_RESOURCE_PATH_PREFIX: &str = "resources/";
 
 /// A safe wrapper around Ultralight's ULConfig type.
 pub struct Config {
     raw: ULConfig,
+    resource_path_prefix: std::string::String,
+    validate_resources: bool,
 }
 
 impl Config {
@@ -19,10 +27,19 @@ impl Config {
     pub fn new() -> Self {
         unsafe {
             let raw = ulCreateConfig();
-            Self { raw }
+            Self {
+                raw,
+                resource_path_prefix: DEFAULT_RESOURCE_PATH_PREFIX.to_owned(),
+                validate_resources: true,
+            }
         }
     }
 
+    /// Create a builder for a config, starting from Ultralight's defaults.
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::new()
+    }
+
     /// Get a reference to the raw ULConfig.
     pub fn raw(&self) -> ULConfig {
         self.raw
@@ -43,9 +60,28 @@ impl Config {
         unsafe {
             ulConfigSetResourcePathPrefix(self.raw, prefix_str.raw());
         }
+        self.resource_path_prefix = prefix.to_owned();
+        self
+    }
+
+    /// Get the relative path to the resources folder.
+    pub fn resource_path_prefix(&self) -> &str {
+        &self.resource_path_prefix
+    }
+
+    /// Set whether `Renderer::try_new` checks that the resources folder exists.
+    ///
+    /// Disable this when resources are served by a custom file system.
+    pub fn set_validate_resources(&mut self, enabled: bool) -> &mut Self {
+        self.validate_resources = enabled;
         self
     }
 
+    /// Check if `Renderer::try_new` checks that the resources folder exists.
+    pub fn validates_resources(&self) -> bool {
+        self.validate_resources
+    }
+
     /// Set the winding order for front-facing triangles.
     pub fn set_face_winding(&mut self, winding: ULFaceWinding) -> &mut Self {
         unsafe {
@@ -176,6 +212,112 @@ impl Config {
     }
 }
 
+/// A builder for Config.
+///
+/// Options that are not set keep Ultralight's defaults.
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    /// Create a new builder with default values.
+    pub fn new() -> Self {
+        Self {
+            config: Config::new(),
+        }
+    }
+
+    /// Set the cache path for persistent Session data.
+    pub fn cache_path(mut self, path: &Path) -> Self {
+        self.config.set_cache_path(&path.to_string_lossy());
+        self
+    }
+
+    /// Set the relative path to the resources folder.
+    pub fn resource_path_prefix(mut self, prefix: &str) -> Self {
+        self.config.set_resource_path_prefix(prefix);
+        self
+    }
+
+    /// Set the font hinting algorithm.
+    pub fn font_hinting(mut self, hinting: ULFontHinting) -> Self {
+        self.config.set_font_hinting(hinting);
+        self
+    }
+
+    /// Set the gamma to use when composing font glyphs.
+    pub fn font_gamma(mut self, gamma: f64) -> Self {
+        self.config.set_font_gamma(gamma);
+        self
+    }
+
+    /// Set the number of threads to use in the Renderer.
+    pub fn num_renderer_threads(mut self, num_threads: u32) -> Self {
+        self.config.set_num_renderer_threads(num_threads);
+        self
+    }
+
+    /// Set the size of WebCore's memory cache in bytes.
+    pub fn memory_cache_size(mut self, size: u32) -> Self {
+        self.config.set_memory_cache_size(size);
+        self
+    }
+
+    /// Set the global user-defined CSS string.
+    pub fn user_stylesheet(mut self, css: &str) -> Self {
+        self.config.set_user_stylesheet(css);
+        self
+    }
+
+    /// Build the config.
+    pub fn build(self) -> Config {
+        self.config
+    }
+}
+
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check that a resources folder contains the files Ultralight needs at startup.
+pub(crate) fn check_resources(dir: &Path) -> Result<(), Error> {
+    let entries = fs::read_dir(dir).map_err(|err| {
+        Error::CreationFailed(format!(
+            "resources folder {} could not be read: {}",
+            dir.display(),
+            err
+        ))
+    })?;
+
+    let names: Vec<std::string::String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+
+    let mut missing = Vec::new();
+    if !names.iter().any(|name| name == "cacert.pem") {
+        missing.push("cacert.pem");
+    }
+    if !names
+        .iter()
+        .any(|name| name.starts_with("icudt") && name.ends_with(".dat"))
+    {
+        missing.push("icudt*.dat");
+    }
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::CreationFailed(format!(
+            "resources folder {} is missing {}",
+            dir.display(),
+            missing.join(", ")
+        )))
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self::new()
@@ -191,3 +333,64 @@ impl Drop for Config {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// A directory under the system temp dir, removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("ul-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn missing_files(dir: &Path) -> std::string::String {
+        match check_resources(dir) {
+            Err(Error::CreationFailed(message)) => message,
+            result => panic!("expected CreationFailed, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn check_resources_accepts_a_complete_folder() {
+        let dir = TempDir::new("complete-resources");
+        fs::write(dir.0.join("cacert.pem"), "").unwrap();
+        fs::write(dir.0.join("icudt67l.dat"), "").unwrap();
+        assert!(check_resources(&dir.0).is_ok());
+    }
+
+    #[test]
+    fn check_resources_lists_missing_files() {
+        let dir = TempDir::new("empty-resources");
+        let message = missing_files(&dir.0);
+        assert!(
+            message.ends_with("is missing cacert.pem, icudt*.dat"),
+            "{message}"
+        );
+
+        fs::write(dir.0.join("cacert.pem"), "").unwrap();
+        fs::write(dir.0.join("icudt.txt"), "").unwrap();
+        let message = missing_files(&dir.0);
+        assert!(message.ends_with("is missing icudt*.dat"), "{message}");
+    }
+
+    #[test]
+    fn check_resources_reports_a_missing_folder() {
+        let dir = TempDir::new("missing-resources");
+        let message = missing_files(&dir.0.join("resources"));
+        assert!(message.contains("could not be read"), "{message}");
+    }
+}
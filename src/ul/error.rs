@@ -13,8 +13,26 @@ pub enum Error {
     InvalidOperation(&'static str),
     
     /// A JavaScript error occurred during evaluation.
+    ///
+    /// `View::evaluate_script` reports exceptions this way, as a string.
     JavaScriptError(String),
     
+    /// A JavaScript exception was thrown during evaluation.
+    ///
+    /// `View::evaluate_script_with` reports exceptions this way.
+    JavaScriptException {
+        /// The exception converted to a string, such as `TypeError: x is not a function`.
+        message: String,
+        /// The URL of the script that threw, if known.
+        source_url: Option<String>,
+        /// The line the exception was thrown from, if known.
+        line: Option<u32>,
+        /// The column the exception was thrown from, if known.
+        column: Option<u32>,
+        /// The JavaScript stack trace, if available.
+        stack: Option<String>,
+    },
+    
     /// Invalid UTF-8 was encountered in a string.
     InvalidUtf8(Utf8Error),
     
@@ -29,6 +47,9 @@ pub enum Error {
     
     /// An error occurred in the Ultralight API.
     UltralightError(&'static str),
+    
+    /// An Ultralight object could not be created.
+    CreationFailed(String),
 }
 
 impl fmt::Display for Error {
@@ -37,11 +58,26 @@ impl fmt::Display for Error {
             Error::NullReference(desc) => write!(f, "Null reference: {}", desc),
             Error::InvalidOperation(desc) => write!(f, "Invalid operation: {}", desc),
             Error::JavaScriptError(desc) => write!(f, "JavaScript error: {}", desc),
+            Error::JavaScriptException { message, source_url, line, column, .. } => {
+                write!(f, "JavaScript error: {}", message)?;
+                if source_url.is_none() && line.is_none() {
+                    return Ok(());
+                }
+                write!(f, " (at {}", source_url.as_deref().unwrap_or("<anonymous>"))?;
+                if let Some(line) = line {
+                    write!(f, ":{}", line)?;
+                    if let Some(column) = column {
+                        write!(f, ":{}", column)?;
+                    }
+                }
+                write!(f, ")")
+            }
             Error::InvalidUtf8(err) => write!(f, "Invalid UTF-8: {}", err),
             Error::IoError(err) => write!(f, "I/O error: {}", err),
             Error::InvalidArgument(desc) => write!(f, "Invalid argument: {}", desc),
             Error::ResourceDestroyed(desc) => write!(f, "Resource destroyed: {}", desc),
             Error::UltralightError(desc) => write!(f, "Ultralight error: {}", desc),
+            Error::CreationFailed(desc) => write!(f, "Creation failed: {}", desc),
         }
     }
 }
@@ -66,4 +102,48 @@ impl From<io::Error> for Error {
     fn from(err: io::Error) -> Self {
         Error::IoError(err)
     }
-}
\ No newline at end of file
+}
+
+/// Keeps the location and stack of JavaScriptCore exceptions.
+impl From<crate::javascript_core::Error> for Error {
+    fn from(err: crate::javascript_core::Error) -> Self {
+        match err {
+            crate::javascript_core::Error::JSException { message, source_url, line, column, stack_trace, .. } => {
+                Error::JavaScriptException { message, source_url, line, column, stack: stack_trace }
+            }
+            err => Error::JavaScriptError(err.message().to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn javascript_core_exception_keeps_its_location() {
+        let error = Error::from(crate::javascript_core::Error::JSException {
+            message: "TypeError: x is not a function".to_owned(),
+            source_url: Some("test.js".to_owned()),
+            line: Some(3),
+            column: Some(7),
+            stack_trace: Some("@test.js:3:7".to_owned()),
+            exception: None,
+        });
+        
+        let Error::JavaScriptException { message, source_url, line, column, stack } = error else {
+            panic!("expected a JavaScript exception, got {:?}", error);
+        };
+        assert_eq!(message, "TypeError: x is not a function");
+        assert_eq!(source_url.as_deref(), Some("test.js"));
+        assert_eq!(line, Some(3));
+        assert_eq!(column, Some(7));
+        assert_eq!(stack.as_deref(), Some("@test.js:3:7"));
+    }
+
+    #[test]
+    fn other_javascript_core_errors_keep_only_their_message() {
+        let error = Error::from(crate::javascript_core::Error::JSError("execution timed out".to_owned()));
+        assert!(matches!(&error, Error::JavaScriptError(message) if message == "execution timed out"), "got {:?}", error);
+    }
+}
@@ -6,6 +6,7 @@ use crate::ul::ffi::{
     ulDestroyScrollEvent,
 };
 use crate::ul::string::String;
+use std::mem;
 
 pub use crate::ul::ffi::{
     ULGamepadEventType as GamepadEventType, ULKeyEventType as KeyEventType,
@@ -13,6 +14,10 @@ pub use crate::ul::ffi::{
     ULScrollEventType as ScrollEventType,
 };
 
+// Each wrapper owns its event and destroys it on drop, unless released with
+// `into_raw`. Ultralight copies an event when it is fired, so a wrapper may be
+// dropped or fired again afterwards.
+
 /// A safe wrapper around Ultralight's ULKeyEvent type.
 pub struct KeyEvent {
     raw: ULKeyEvent,
@@ -80,6 +85,16 @@ impl KeyEvent {
     pub fn raw(&self) -> ULKeyEvent {
         self.raw
     }
+
+    /// Release ownership of the raw ULKeyEvent.
+    ///
+    /// The event is no longer destroyed when this wrapper is dropped; the caller
+    /// must destroy it with `ulDestroyKeyEvent`.
+    pub fn into_raw(self) -> ULKeyEvent {
+        let raw = self.raw;
+        mem::forget(self);
+        raw
+    }
 }
 
 impl Drop for KeyEvent {
@@ -101,10 +116,40 @@ impl MouseEvent {
         }
     }
 
+    /// Create a mouse-moved event with no button pressed.
+    pub fn moved(x: i32, y: i32) -> Self {
+        Self::new(
+            MouseEventType::kMouseEventType_MouseMoved,
+            x,
+            y,
+            MouseButton::kMouseButton_None,
+        )
+    }
+
+    /// Create a mouse-down event.
+    pub fn down(x: i32, y: i32, button: MouseButton) -> Self {
+        Self::new(MouseEventType::kMouseEventType_MouseDown, x, y, button)
+    }
+
+    /// Create a mouse-up event.
+    pub fn up(x: i32, y: i32, button: MouseButton) -> Self {
+        Self::new(MouseEventType::kMouseEventType_MouseUp, x, y, button)
+    }
+
     /// Get a reference to the raw ULMouseEvent.
     pub fn raw(&self) -> ULMouseEvent {
         self.raw
     }
+
+    /// Release ownership of the raw ULMouseEvent.
+    ///
+    /// The event is no longer destroyed when this wrapper is dropped; the caller
+    /// must destroy it with `ulDestroyMouseEvent`.
+    pub fn into_raw(self) -> ULMouseEvent {
+        let raw = self.raw;
+        mem::forget(self);
+        raw
+    }
 }
 
 impl Drop for MouseEvent {
@@ -126,10 +171,38 @@ impl ScrollEvent {
         }
     }
 
+    /// Create a scroll event measured in pixels.
+    pub fn by_pixel(delta_x: i32, delta_y: i32) -> Self {
+        Self::new(
+            ScrollEventType::kScrollEventType_ScrollByPixel,
+            delta_x,
+            delta_y,
+        )
+    }
+
+    /// Create a scroll event measured in pages.
+    pub fn by_page(delta_x: i32, delta_y: i32) -> Self {
+        Self::new(
+            ScrollEventType::kScrollEventType_ScrollByPage,
+            delta_x,
+            delta_y,
+        )
+    }
+
     /// Get a reference to the raw ULScrollEvent.
     pub fn raw(&self) -> ULScrollEvent {
         self.raw
     }
+
+    /// Release ownership of the raw ULScrollEvent.
+    ///
+    /// The event is no longer destroyed when this wrapper is dropped; the caller
+    /// must destroy it with `ulDestroyScrollEvent`.
+    pub fn into_raw(self) -> ULScrollEvent {
+        let raw = self.raw;
+        mem::forget(self);
+        raw
+    }
 }
 
 impl Drop for ScrollEvent {
@@ -155,6 +228,16 @@ impl GamepadEvent {
     pub fn raw(&self) -> ULGamepadEvent {
         self.raw
     }
+
+    /// Release ownership of the raw ULGamepadEvent.
+    ///
+    /// The event is no longer destroyed when this wrapper is dropped; the caller
+    /// must destroy it with `ulDestroyGamepadEvent`.
+    pub fn into_raw(self) -> ULGamepadEvent {
+        let raw = self.raw;
+        mem::forget(self);
+        raw
+    }
 }
 
 impl Drop for GamepadEvent {
@@ -180,6 +263,16 @@ impl GamepadAxisEvent {
     pub fn raw(&self) -> ULGamepadAxisEvent {
         self.raw
     }
+
+    /// Release ownership of the raw ULGamepadAxisEvent.
+    ///
+    /// The event is no longer destroyed when this wrapper is dropped; the caller
+    /// must destroy it with `ulDestroyGamepadAxisEvent`.
+    pub fn into_raw(self) -> ULGamepadAxisEvent {
+        let raw = self.raw;
+        mem::forget(self);
+        raw
+    }
 }
 
 impl Drop for GamepadAxisEvent {
@@ -205,6 +298,16 @@ impl GamepadButtonEvent {
     pub fn raw(&self) -> ULGamepadButtonEvent {
         self.raw
     }
+
+    /// Release ownership of the raw ULGamepadButtonEvent.
+    ///
+    /// The event is no longer destroyed when this wrapper is dropped; the caller
+    /// must destroy it with `ulDestroyGamepadButtonEvent`.
+    pub fn into_raw(self) -> ULGamepadButtonEvent {
+        let raw = self.raw;
+        mem::forget(self);
+        raw
+    }
 }
 
 impl Drop for GamepadButtonEvent {
@@ -0,0 +1,147 @@
+use crate::ul::buffer::Buffer;
+use crate::ul::error::Error;
+use crate::ul::ffi::{
+    ULFontFile, ULFontLoader, ULString, ulDestroyFontFile, ulFontFileCreateFromBuffer,
+    ulFontFileCreateFromFilePath,
+};
+use crate::ul::string::String;
+use std::cell::RefCell;
+use std::mem;
+use std::os::raw::c_int;
+use std::path::Path;
+use std::ptr;
+
+/// A safe wrapper around Ultralight's ULFontFile type.
+pub struct FontFile {
+    raw: ULFontFile,
+}
+
+impl FontFile {
+    /// Create a font file from an on-disk file path.
+    pub fn from_path(path: &Path) -> Result<Self, Error> {
+        let path = path
+            .to_str()
+            .ok_or(Error::InvalidArgument("Font path is not valid UTF-8"))?;
+        let path_str = String::try_from_str(path)?;
+        Self::from_raw_checked(unsafe { ulFontFileCreateFromFilePath(path_str.raw()) })
+    }
+
+    /// Create a font file from a copy of in-memory font data.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        Self::from_buffer(&Buffer::from_copy(data))
+    }
+
+    /// Create a font file from an in-memory buffer.
+    pub fn from_buffer(buffer: &Buffer) -> Result<Self, Error> {
+        Self::from_raw_checked(unsafe { ulFontFileCreateFromBuffer(buffer.raw()) })
+    }
+
+    fn from_raw_checked(raw: ULFontFile) -> Result<Self, Error> {
+        if raw.is_null() {
+            return Err(Error::CreationFailed(
+                "failed to create font file".to_owned(),
+            ));
+        }
+
+        Ok(Self { raw })
+    }
+
+    /// Get a reference to the raw ULFontFile.
+    pub fn raw(&self) -> ULFontFile {
+        self.raw
+    }
+
+    /// Release ownership of the raw ULFontFile, e.g. to return it to Ultralight.
+    pub fn into_raw(self) -> ULFontFile {
+        let raw = self.raw;
+        mem::forget(self);
+        raw
+    }
+}
+
+impl Drop for FontFile {
+    fn drop(&mut self) {
+        if !self.raw.is_null() {
+            unsafe {
+                ulDestroyFontFile(self.raw);
+            }
+        }
+    }
+}
+
+/// A font loader that Ultralight uses to resolve font families to font files.
+///
+/// Implement this to provide fonts on systems without usable system fonts, such
+/// as headless servers, e.g. by embedding TTF files.
+pub trait FontLoader {
+    /// Get the font family to use when no other font matches.
+    fn fallback_font(&self) -> std::string::String;
+
+    /// Get the font family to use for characters that the requested font lacks.
+    fn fallback_for_chars(&self, chars: &str, weight: i32, italic: bool) -> std::string::String;
+
+    /// Load the font file for a font family, or `None` if the family isn't available.
+    fn load(&self, family: &str, weight: i32, italic: bool) -> Option<FontFile>;
+}
+
+thread_local! {
+    static ACTIVE_FONT_LOADER: RefCell<Option<Box<dyn FontLoader>>> = RefCell::new(None);
+}
+
+/// Install a font loader as the active font loader for the current thread.
+pub(crate) fn set_active_font_loader<F: FontLoader + 'static>(font_loader: F) {
+    ACTIVE_FONT_LOADER.with(|cell| {
+        *cell.borrow_mut() = Some(Box::new(font_loader));
+    });
+}
+
+fn with_font_loader<R: Default>(f: impl FnOnce(&dyn FontLoader) -> R) -> R {
+    ACTIVE_FONT_LOADER.with(|cell| match cell.borrow().as_ref() {
+        Some(font_loader) => f(font_loader.as_ref()),
+        None => R::default(),
+    })
+}
+
+/// Create a ULString whose ownership passes to Ultralight.
+fn into_ul_string(s: &str) -> ULString {
+    let string = String::from_utf8(s.as_bytes());
+    let raw = string.raw();
+    mem::forget(string);
+    raw
+}
+
+// Trampoline functions for the callbacks
+extern "C" fn get_fallback_font_trampoline() -> ULString {
+    into_ul_string(&with_font_loader(|l| l.fallback_font()))
+}
+
+extern "C" fn get_fallback_font_for_characters_trampoline(
+    characters: ULString,
+    weight: c_int,
+    italic: bool,
+) -> ULString {
+    let characters = unsafe { String::from_raw(characters, false) };
+    let family = match characters.as_str() {
+        Ok(characters) => with_font_loader(|l| l.fallback_for_chars(characters, weight, italic)),
+        Err(_) => with_font_loader(|l| l.fallback_font()),
+    };
+    into_ul_string(&family)
+}
+
+extern "C" fn load_trampoline(family: ULString, weight: c_int, italic: bool) -> ULFontFile {
+    let family = unsafe { String::from_raw(family, false) };
+    family
+        .as_str()
+        .ok()
+        .and_then(|family| with_font_loader(|l| l.load(family, weight, italic)))
+        .map_or(ptr::null_mut(), FontFile::into_raw)
+}
+
+/// Build a raw ULFontLoader that dispatches to the active font loader for the current thread.
+pub(crate) fn raw_font_loader() -> ULFontLoader {
+    ULFontLoader {
+        get_fallback_font: get_fallback_font_trampoline,
+        get_fallback_font_for_characters: get_fallback_font_for_characters_trampoline,
+        load: load_trampoline,
+    }
+}
@@ -0,0 +1,571 @@
+use crate::ul::bitmap::Bitmap;
+use crate::ul::ffi::{
+    ULBitmap, ULCommand, ULCommandList, ULGPUDriver, ULGPUState, ULIndexBuffer, ULIndexType,
+    ULRenderBuffer, ULVertex_2f_4ub_2f, ULVertex_2f_4ub_2f_2f_28f, ULVertexBuffer,
+};
+use crate::ul::geometry::IntRect;
+use std::cell::RefCell;
+use std::mem;
+use std::os::raw::c_uint;
+use std::slice;
+
+pub use crate::ul::ffi::{
+    ULCommandType as CommandType, ULShaderType as ShaderType,
+    ULVertexBufferFormat as VertexBufferFormat,
+};
+
+/// A vertex in the `2f_4ub_2f` format (used for path geometry).
+pub type Vertex2f4ub2f = ULVertex_2f_4ub_2f;
+
+/// A vertex in the `2f_4ub_2f_2f_28f` format (used for quad geometry).
+pub type Vertex2f4ub2f2f28f = ULVertex_2f_4ub_2f_2f_28f;
+
+/// A 4x4 column-major transformation matrix.
+pub type Matrix4x4 = [f32; 16];
+
+/// Description of an offscreen render buffer.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderBuffer {
+    pub texture_id: u32,
+    pub width: u32,
+    pub height: u32,
+    pub has_stencil_buffer: bool,
+    pub has_depth_buffer: bool,
+}
+
+impl RenderBuffer {
+    /// Create a render buffer from a raw ULRenderBuffer.
+    pub fn from_raw(raw: ULRenderBuffer) -> Self {
+        Self {
+            texture_id: raw.texture_id,
+            width: raw.width,
+            height: raw.height,
+            has_stencil_buffer: raw.has_stencil_buffer,
+            has_depth_buffer: raw.has_depth_buffer,
+        }
+    }
+}
+
+/// A borrowed view of vertex data passed to the GPU driver.
+///
+/// The data is only valid for the duration of the driver callback.
+#[derive(Debug, Clone, Copy)]
+pub struct VertexBuffer<'a> {
+    format: VertexBufferFormat,
+    data: &'a [u8],
+}
+
+impl<'a> VertexBuffer<'a> {
+    /// Create a vertex buffer view from a raw ULVertexBuffer.
+    ///
+    /// # Safety
+    ///
+    /// The buffer's data pointer must be valid for `size` bytes for the lifetime `'a`.
+    pub unsafe fn from_raw(raw: &ULVertexBuffer) -> Self {
+        let data = if raw.data.is_null() || raw.size == 0 {
+            &[][..]
+        } else {
+            unsafe { slice::from_raw_parts(raw.data as *const u8, raw.size as usize) }
+        };
+
+        Self {
+            format: raw.format,
+            data,
+        }
+    }
+
+    /// Get the vertex format.
+    pub fn format(&self) -> VertexBufferFormat {
+        self.format
+    }
+
+    /// Get the raw vertex bytes.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Get the vertices if the buffer is in the `2f_4ub_2f` format.
+    pub fn as_2f_4ub_2f(&self) -> Option<&'a [Vertex2f4ub2f]> {
+        match self.format {
+            VertexBufferFormat::kVertexBufferFormat_2f_4ub_2f => Some(self.cast()),
+            _ => None,
+        }
+    }
+
+    /// Get the vertices if the buffer is in the `2f_4ub_2f_2f_28f` format.
+    pub fn as_2f_4ub_2f_2f_28f(&self) -> Option<&'a [Vertex2f4ub2f2f28f]> {
+        match self.format {
+            VertexBufferFormat::kVertexBufferFormat_2f_4ub_2f_2f_28f => Some(self.cast()),
+            _ => None,
+        }
+    }
+
+    fn cast<T>(&self) -> &'a [T] {
+        // Vertex structs are packed, so any byte offset is suitably aligned.
+        let len = self.data.len() / mem::size_of::<T>();
+        unsafe { slice::from_raw_parts(self.data.as_ptr() as *const T, len) }
+    }
+}
+
+/// A borrowed view of index data passed to the GPU driver.
+///
+/// The data is only valid for the duration of the driver callback.
+#[derive(Debug, Clone, Copy)]
+pub struct IndexBuffer<'a> {
+    indices: &'a [ULIndexType],
+}
+
+impl<'a> IndexBuffer<'a> {
+    /// Create an index buffer view from a raw ULIndexBuffer.
+    ///
+    /// # Safety
+    ///
+    /// The buffer's data pointer must be valid for `size` bytes for the lifetime `'a`.
+    pub unsafe fn from_raw(raw: &ULIndexBuffer) -> Self {
+        let len = raw.size as usize / mem::size_of::<ULIndexType>();
+        let indices = if raw.data.is_null() || len == 0 {
+            &[][..]
+        } else {
+            unsafe { slice::from_raw_parts(raw.data as *const ULIndexType, len) }
+        };
+
+        Self { indices }
+    }
+
+    /// Get the indices.
+    pub fn indices(&self) -> &'a [u32] {
+        self.indices
+    }
+
+    /// Get the number of indices.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Check if the buffer contains no indices.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+/// The GPU state to apply for a single draw command.
+#[derive(Debug, Clone)]
+pub struct GpuState {
+    pub viewport_width: u32,
+    pub viewport_height: u32,
+    pub transform: Matrix4x4,
+    pub enable_texturing: bool,
+    pub enable_blend: bool,
+    pub shader_type: ShaderType,
+    pub render_buffer_id: u32,
+    pub texture_1_id: u32,
+    pub texture_2_id: u32,
+    pub texture_3_id: u32,
+    pub uniform_scalar: [f32; 8],
+    pub uniform_vector: [[f32; 4]; 8],
+    pub clip: Vec<Matrix4x4>,
+    pub scissor_rect: Option<IntRect>,
+}
+
+impl GpuState {
+    /// Create a GPU state from a raw ULGPUState.
+    pub fn from_raw(raw: &ULGPUState) -> Self {
+        let clip_size = (raw.clip_size as usize).min(raw.clip.len());
+
+        Self {
+            viewport_width: raw.viewport_width,
+            viewport_height: raw.viewport_height,
+            transform: raw.transform.data,
+            enable_texturing: raw.enable_texturing,
+            enable_blend: raw.enable_blend,
+            shader_type: match raw.shader_type {
+                0 => ShaderType::kShaderType_Fill,
+                _ => ShaderType::kShaderType_FillPath,
+            },
+            render_buffer_id: raw.render_buffer_id,
+            texture_1_id: raw.texture_1_id,
+            texture_2_id: raw.texture_2_id,
+            texture_3_id: raw.texture_3_id,
+            uniform_scalar: raw.uniform_scalar,
+            uniform_vector: raw.uniform_vector.map(|v| v.value),
+            clip: raw.clip[..clip_size].iter().map(|m| m.data).collect(),
+            scissor_rect: if raw.enable_scissor {
+                Some(IntRect::from_raw(raw.scissor_rect))
+            } else {
+                None
+            },
+        }
+    }
+}
+
+/// A single command in a command list.
+#[derive(Debug, Clone)]
+pub struct Command {
+    pub command_type: CommandType,
+    pub gpu_state: GpuState,
+    pub geometry_id: u32,
+    pub indices_count: u32,
+    pub indices_offset: u32,
+}
+
+impl Command {
+    /// Create a command from a raw ULCommand.
+    pub fn from_raw(raw: &ULCommand) -> Self {
+        Self {
+            command_type: match raw.command_type {
+                0 => CommandType::kCommandType_ClearRenderBuffer,
+                _ => CommandType::kCommandType_DrawGeometry,
+            },
+            gpu_state: GpuState::from_raw(&raw.gpu_state),
+            geometry_id: raw.geometry_id,
+            indices_count: raw.indices_count,
+            indices_offset: raw.indices_offset,
+        }
+    }
+}
+
+/// Trait for implementing a custom GPU driver.
+///
+/// The renderer calls into the driver from the thread that calls
+/// `Renderer::render`, so the driver must be installed on that thread.
+pub trait GpuDriver {
+    /// Called before any state (textures, render buffers, geometry) is updated.
+    fn begin_synchronize(&mut self) {}
+
+    /// Called after all state has been updated.
+    fn end_synchronize(&mut self) {}
+
+    /// Get the next available texture ID.
+    fn next_texture_id(&mut self) -> u32;
+
+    /// Create a texture with a certain ID and optional bitmap.
+    ///
+    /// If the bitmap is empty, the texture is used as a render target.
+    fn create_texture(&mut self, texture_id: u32, bitmap: &Bitmap);
+
+    /// Update an existing non-RTT texture with new bitmap data.
+    fn update_texture(&mut self, texture_id: u32, bitmap: &Bitmap);
+
+    /// Destroy a texture.
+    fn destroy_texture(&mut self, texture_id: u32);
+
+    /// Get the next available render buffer ID.
+    fn next_render_buffer_id(&mut self) -> u32;
+
+    /// Create a render buffer with a certain ID and buffer description.
+    fn create_render_buffer(&mut self, render_buffer_id: u32, buffer: RenderBuffer);
+
+    /// Destroy a render buffer.
+    fn destroy_render_buffer(&mut self, render_buffer_id: u32);
+
+    /// Get the next available geometry ID.
+    fn next_geometry_id(&mut self) -> u32;
+
+    /// Create geometry with a certain ID and vertex/index data.
+    fn create_geometry(&mut self, geometry_id: u32, vertices: VertexBuffer, indices: IndexBuffer);
+
+    /// Update existing geometry with new vertex/index data.
+    fn update_geometry(&mut self, geometry_id: u32, vertices: VertexBuffer, indices: IndexBuffer);
+
+    /// Destroy geometry.
+    fn destroy_geometry(&mut self, geometry_id: u32);
+
+    /// Update the pending command list.
+    fn update_command_list(&mut self, commands: &[Command]);
+}
+
+// Thread-local storage for the active driver
+thread_local! {
+    static ACTIVE_GPU_DRIVER: RefCell<Option<Box<dyn GpuDriver>>> = RefCell::new(None);
+}
+
+/// Install a driver as the active driver for the current thread.
+pub(crate) fn set_active_driver<D: GpuDriver + 'static>(driver: D) {
+    ACTIVE_GPU_DRIVER.with(|cell| {
+        *cell.borrow_mut() = Some(Box::new(driver));
+    });
+}
+
+fn with_driver<R: Default>(f: impl FnOnce(&mut dyn GpuDriver) -> R) -> R {
+    ACTIVE_GPU_DRIVER.with(|cell| match cell.borrow_mut().as_mut() {
+        Some(driver) => f(driver.as_mut()),
+        None => R::default(),
+    })
+}
+
+// Trampoline functions for the callbacks
+extern "C" fn begin_synchronize_trampoline() {
+    with_driver(|d| d.begin_synchronize());
+}
+
+extern "C" fn end_synchronize_trampoline() {
+    with_driver(|d| d.end_synchronize());
+}
+
+extern "C" fn next_texture_id_trampoline() -> c_uint {
+    with_driver(|d| d.next_texture_id())
+}
+
+extern "C" fn create_texture_trampoline(texture_id: c_uint, bitmap: ULBitmap) {
+    let bitmap = unsafe { Bitmap::from_raw(bitmap, false) };
+    with_driver(|d| d.create_texture(texture_id, &bitmap));
+}
+
+extern "C" fn update_texture_trampoline(texture_id: c_uint, bitmap: ULBitmap) {
+    let bitmap = unsafe { Bitmap::from_raw(bitmap, false) };
+    with_driver(|d| d.update_texture(texture_id, &bitmap));
+}
+
+extern "C" fn destroy_texture_trampoline(texture_id: c_uint) {
+    with_driver(|d| d.destroy_texture(texture_id));
+}
+
+extern "C" fn next_render_buffer_id_trampoline() -> c_uint {
+    with_driver(|d| d.next_render_buffer_id())
+}
+
+extern "C" fn create_render_buffer_trampoline(render_buffer_id: c_uint, buffer: ULRenderBuffer) {
+    with_driver(|d| d.create_render_buffer(render_buffer_id, RenderBuffer::from_raw(buffer)));
+}
+
+extern "C" fn destroy_render_buffer_trampoline(render_buffer_id: c_uint) {
+    with_driver(|d| d.destroy_render_buffer(render_buffer_id));
+}
+
+extern "C" fn next_geometry_id_trampoline() -> c_uint {
+    with_driver(|d| d.next_geometry_id())
+}
+
+extern "C" fn create_geometry_trampoline(
+    geometry_id: c_uint,
+    vertices: ULVertexBuffer,
+    indices: ULIndexBuffer,
+) {
+    let (vertices, indices) = unsafe {
+        (
+            VertexBuffer::from_raw(&vertices),
+            IndexBuffer::from_raw(&indices),
+        )
+    };
+    with_driver(|d| d.create_geometry(geometry_id, vertices, indices));
+}
+
+extern "C" fn update_geometry_trampoline(
+    geometry_id: c_uint,
+    vertices: ULVertexBuffer,
+    indices: ULIndexBuffer,
+) {
+    let (vertices, indices) = unsafe {
+        (
+            VertexBuffer::from_raw(&vertices),
+            IndexBuffer::from_raw(&indices),
+        )
+    };
+    with_driver(|d| d.update_geometry(geometry_id, vertices, indices));
+}
+
+extern "C" fn destroy_geometry_trampoline(geometry_id: c_uint) {
+    with_driver(|d| d.destroy_geometry(geometry_id));
+}
+
+extern "C" fn update_command_list_trampoline(list: ULCommandList) {
+    let commands: Vec<Command> = if list.commands.is_null() || list.size == 0 {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(list.commands, list.size as usize) }
+            .iter()
+            .map(Command::from_raw)
+            .collect()
+    };
+    with_driver(|d| d.update_command_list(&commands));
+}
+
+/// Build a raw ULGPUDriver that dispatches to the active driver for the current thread.
+pub(crate) fn raw_driver() -> ULGPUDriver {
+    ULGPUDriver {
+        begin_synchronize: begin_synchronize_trampoline,
+        end_synchronize: end_synchronize_trampoline,
+        next_texture_id: next_texture_id_trampoline,
+        create_texture: create_texture_trampoline,
+        update_texture: update_texture_trampoline,
+        destroy_texture: destroy_texture_trampoline,
+        next_render_buffer_id: next_render_buffer_id_trampoline,
+        create_render_buffer: create_render_buffer_trampoline,
+        destroy_render_buffer: destroy_render_buffer_trampoline,
+        next_geometry_id: next_geometry_id_trampoline,
+        create_geometry: create_geometry_trampoline,
+        update_geometry: update_geometry_trampoline,
+        destroy_geometry: destroy_geometry_trampoline,
+        update_command_list: update_command_list_trampoline,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ul::ffi::ULVertexBufferFormat;
+    use std::ptr;
+    use std::rc::Rc;
+
+    /// A driver that draws nothing and records which callbacks fired.
+    #[derive(Default)]
+    struct RecordingDriver {
+        calls: Rc<RefCell<Vec<std::string::String>>>,
+        next_id: u32,
+    }
+
+    impl RecordingDriver {
+        fn record(&self, call: impl Into<std::string::String>) {
+            self.calls.borrow_mut().push(call.into());
+        }
+
+        fn next_id(&mut self) -> u32 {
+            self.next_id += 1;
+            self.next_id
+        }
+    }
+
+    impl GpuDriver for RecordingDriver {
+        fn begin_synchronize(&mut self) {
+            self.record("begin_synchronize");
+        }
+
+        fn end_synchronize(&mut self) {
+            self.record("end_synchronize");
+        }
+
+        fn next_texture_id(&mut self) -> u32 {
+            self.next_id()
+        }
+
+        fn create_texture(&mut self, texture_id: u32, _bitmap: &Bitmap) {
+            self.record(format!("create_texture {texture_id}"));
+        }
+
+        fn update_texture(&mut self, texture_id: u32, _bitmap: &Bitmap) {
+            self.record(format!("update_texture {texture_id}"));
+        }
+
+        fn destroy_texture(&mut self, texture_id: u32) {
+            self.record(format!("destroy_texture {texture_id}"));
+        }
+
+        fn next_render_buffer_id(&mut self) -> u32 {
+            self.next_id()
+        }
+
+        fn create_render_buffer(&mut self, render_buffer_id: u32, buffer: RenderBuffer) {
+            self.record(format!(
+                "create_render_buffer {render_buffer_id} {}x{}",
+                buffer.width, buffer.height
+            ));
+        }
+
+        fn destroy_render_buffer(&mut self, render_buffer_id: u32) {
+            self.record(format!("destroy_render_buffer {render_buffer_id}"));
+        }
+
+        fn next_geometry_id(&mut self) -> u32 {
+            self.next_id()
+        }
+
+        fn create_geometry(
+            &mut self,
+            geometry_id: u32,
+            vertices: VertexBuffer,
+            indices: IndexBuffer,
+        ) {
+            self.record(format!(
+                "create_geometry {geometry_id} {} {:?}",
+                vertices.as_bytes().len(),
+                indices.indices()
+            ));
+        }
+
+        fn update_geometry(
+            &mut self,
+            geometry_id: u32,
+            _vertices: VertexBuffer,
+            _indices: IndexBuffer,
+        ) {
+            self.record(format!("update_geometry {geometry_id}"));
+        }
+
+        fn destroy_geometry(&mut self, geometry_id: u32) {
+            self.record(format!("destroy_geometry {geometry_id}"));
+        }
+
+        fn update_command_list(&mut self, commands: &[Command]) {
+            self.record(format!("update_command_list {}", commands.len()));
+        }
+    }
+
+    #[test]
+    fn trampolines_dispatch_to_the_active_driver() {
+        let driver = RecordingDriver::default();
+        let calls = Rc::clone(&driver.calls);
+        set_active_driver(driver);
+
+        let raw = raw_driver();
+        let mut vertex_data = [0u8; 20];
+        let mut index_data = [0u32, 1, 2];
+        let vertices = ULVertexBuffer {
+            format: ULVertexBufferFormat::kVertexBufferFormat_2f_4ub_2f,
+            size: vertex_data.len() as c_uint,
+            data: vertex_data.as_mut_ptr(),
+        };
+        let indices = ULIndexBuffer {
+            size: mem::size_of_val(&index_data) as c_uint,
+            data: index_data.as_mut_ptr() as *mut u8,
+        };
+
+        (raw.begin_synchronize)();
+        let texture_id = (raw.next_texture_id)();
+        (raw.create_texture)(texture_id, ptr::null_mut());
+        let render_buffer_id = (raw.next_render_buffer_id)();
+        (raw.create_render_buffer)(
+            render_buffer_id,
+            ULRenderBuffer {
+                texture_id,
+                width: 8,
+                height: 4,
+                has_stencil_buffer: false,
+                has_depth_buffer: false,
+            },
+        );
+        let geometry_id = (raw.next_geometry_id)();
+        (raw.create_geometry)(geometry_id, vertices, indices);
+        (raw.end_synchronize)();
+        (raw.update_command_list)(ULCommandList {
+            size: 0,
+            commands: ptr::null_mut(),
+        });
+        (raw.destroy_geometry)(geometry_id);
+        (raw.destroy_render_buffer)(render_buffer_id);
+        (raw.destroy_texture)(texture_id);
+
+        assert_eq!(
+            *calls.borrow(),
+            [
+                "begin_synchronize",
+                "create_texture 1",
+                "create_render_buffer 2 8x4",
+                "create_geometry 3 20 [0, 1, 2]",
+                "end_synchronize",
+                "update_command_list 0",
+                "destroy_geometry 3",
+                "destroy_render_buffer 2",
+                "destroy_texture 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn trampolines_do_nothing_without_a_driver() {
+        ACTIVE_GPU_DRIVER.with(|cell| cell.borrow_mut().take());
+
+        let raw = raw_driver();
+        assert_eq!((raw.next_texture_id)(), 0);
+        (raw.destroy_texture)(1);
+    }
+}
@@ -60,6 +60,10 @@ impl ImageSource {
     }
 
     /// Add an image source to the provider.
+    ///
+    /// Pages can then reference the image with an `image://id` URL, e.g. in an
+    /// `<img>` element or a CSS `background-image`. Call `invalidate` after changing
+    /// the underlying bitmap or texture to redraw the pages that use it.
     pub fn add_to_provider(id: &str, image_source: &ImageSource) {
         let id_str = String::from_str(id);
         unsafe {
@@ -68,6 +72,8 @@ impl ImageSource {
     }
 
     /// Remove an image source from the provider.
+    ///
+    /// Pages that still reference `image://id` will no longer resolve it.
     pub fn remove_from_provider(id: &str) {
         let id_str = String::from_str(id);
         unsafe {
@@ -0,0 +1,123 @@
+use crate::ul::ffi::{ULLogger, ULString, ulPlatformSetLogger};
+use crate::ul::string::String;
+use std::sync::{Mutex, MutexGuard};
+
+pub use crate::ul::ffi::ULLogLevel as LogLevel;
+
+/// A logger that receives messages logged by Ultralight.
+///
+/// Ultralight logs from its worker threads as well as the renderer thread, so
+/// loggers must be `Send`.
+pub trait Logger: Send {
+    /// Handle a log message.
+    fn log_message(&self, level: LogLevel, message: &str);
+}
+
+/// A logger installed through `Platform`, kept so it can be restored after a capture.
+#[derive(Clone)]
+enum InstalledLogger {
+    Raw(ULLogger),
+    Rust,
+    #[cfg(feature = "app")]
+    Native(std::sync::Arc<dyn Fn() + Send + Sync>),
+}
+
+/// The logger state shared by every thread Ultralight logs from.
+struct Dispatch {
+    installed: Option<InstalledLogger>,
+    rust_logger: Option<Box<dyn Logger>>,
+    captured: Option<Vec<std::string::String>>,
+}
+
+static DISPATCH: Mutex<Dispatch> = Mutex::new(Dispatch {
+    installed: None,
+    rust_logger: None,
+    captured: None,
+});
+
+fn dispatch() -> MutexGuard<'static, Dispatch> {
+    // A logger that panicked leaves nothing inconsistent behind
+    DISPATCH.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn install(logger: &InstalledLogger) {
+    match logger {
+        InstalledLogger::Raw(logger) => unsafe { ulPlatformSetLogger(*logger) },
+        InstalledLogger::Rust => unsafe { ulPlatformSetLogger(raw_logger()) },
+        #[cfg(feature = "app")]
+        InstalledLogger::Native(install) => install(),
+    }
+}
+
+fn set_installed(logger: InstalledLogger, rust_logger: Option<Box<dyn Logger>>) {
+    let mut dispatch = dispatch();
+    install(&logger);
+    dispatch.installed = Some(logger);
+    dispatch.rust_logger = rust_logger;
+}
+
+/// Install a raw logger.
+pub(crate) fn set_raw_logger(logger: ULLogger) {
+    set_installed(InstalledLogger::Raw(logger), None);
+}
+
+/// Install a Rust logger, which receives messages from every thread.
+pub(crate) fn set_rust_logger<L: Logger + 'static>(logger: L) {
+    set_installed(InstalledLogger::Rust, Some(Box::new(logger)));
+}
+
+/// Install a logger provided by a native library, such as the AppCore default
+/// logger. `install` is called now, and again to restore the logger after a capture.
+#[cfg(feature = "app")]
+pub(crate) fn set_native_logger(install: impl Fn() + Send + Sync + 'static) {
+    set_installed(InstalledLogger::Native(std::sync::Arc::new(install)), None);
+}
+
+/// Collect the messages logged while `f` runs.
+///
+/// This temporarily installs the crate's dispatching logger and restores the
+/// logger installed through `Platform` afterwards. A Rust logger still receives
+/// the captured messages; a raw or native logger misses them. A logger installed
+/// by other means, such as `App::new`, can't be restored and stays replaced.
+pub(crate) fn capture(f: impl FnOnce()) -> Vec<std::string::String> {
+    {
+        let mut dispatch = dispatch();
+        dispatch.captured = Some(Vec::new());
+        if !matches!(dispatch.installed, Some(InstalledLogger::Rust)) {
+            unsafe { ulPlatformSetLogger(raw_logger()) };
+        }
+    }
+
+    f();
+
+    let mut dispatch = dispatch();
+    if let Some(logger) = dispatch.installed.clone()
+        && !matches!(logger, InstalledLogger::Rust)
+    {
+        install(&logger);
+    }
+    dispatch.captured.take().unwrap_or_default()
+}
+
+// Trampoline functions for the callbacks
+extern "C" fn log_message_trampoline(level: LogLevel, message: ULString) {
+    let message = unsafe { String::from_raw(message, false) };
+    let Ok(message) = message.as_str() else {
+        return;
+    };
+
+    let mut dispatch = dispatch();
+    if let Some(messages) = dispatch.captured.as_mut() {
+        messages.push(message.to_owned());
+    }
+    if let Some(logger) = dispatch.rust_logger.as_ref() {
+        logger.log_message(level, message);
+    }
+}
+
+/// Build a raw ULLogger that dispatches to the installed Rust logger.
+fn raw_logger() -> ULLogger {
+    ULLogger {
+        log_message: log_message_trampoline,
+    }
+}
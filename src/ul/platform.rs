@@ -1,10 +1,30 @@
 use crate::ul::ffi::{
-    ULClipboard, ULFileSystem, ULFontLoader, ULGPUDriver, ULLogger, ULString, ULSurfaceDefinition,
+    ULClipboard, ULFileSystem, ULFontLoader, ULGPUDriver, ULLogger, ULSurfaceDefinition,
     ulPlatformSetClipboard, ulPlatformSetFileSystem, ulPlatformSetFontLoader,
-    ulPlatformSetGPUDriver, ulPlatformSetLogger, ulPlatformSetSurfaceDefinition,
+    ulPlatformSetGPUDriver, ulPlatformSetSurfaceDefinition,
 };
-use crate::app_core::ffi::ulEnableDefaultLogger;
-use crate::ul::string::String;
+use crate::ul::clipboard::{self, Clipboard};
+use crate::ul::font_loader::{self, FontLoader};
+use crate::ul::gpu_driver::{self, GpuDriver};
+use crate::ul::logger::{self, Logger};
+use crate::ul::surface::{self, CustomSurface, SurfaceDefinition};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Base directory of the AppCore platform file system, if it is enabled.
+static FILE_SYSTEM_BASE_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Get the base directory of the AppCore platform file system, if it is enabled.
+pub(crate) fn file_system_base_dir() -> Option<PathBuf> {
+    FILE_SYSTEM_BASE_DIR.lock().ok()?.clone()
+}
+
+/// Record the base directory of the AppCore platform file system.
+pub(crate) fn set_file_system_base_dir(base_dir: Option<PathBuf>) {
+    if let Ok(mut current) = FILE_SYSTEM_BASE_DIR.lock() {
+        *current = base_dir;
+    }
+}
 
 /// Static methods for configuring the platform.
 pub struct Platform;
@@ -12,74 +32,94 @@ pub struct Platform;
 impl Platform {
     /// Set a custom logger implementation.
     pub fn set_logger(logger: ULLogger) {
-        unsafe {
-            ulPlatformSetLogger(logger);
-        }
+        logger::set_raw_logger(logger);
+    }
+
+    /// Set a Rust logger implementation.
+    ///
+    /// The logger receives messages logged on any thread, including Ultralight's
+    /// worker threads.
+    pub fn set_rust_logger<L: Logger + 'static>(logger: L) {
+        logger::set_rust_logger(logger);
     }
 
     /// Set a custom file system implementation.
     pub fn set_file_system(file_system: ULFileSystem) {
+        set_file_system_base_dir(None);
         unsafe {
             ulPlatformSetFileSystem(file_system);
         }
     }
 
     /// Set a custom font loader implementation.
-    pub fn set_font_loader(font_loader: ULFontLoader) {
-        unsafe {
-            ulPlatformSetFontLoader(font_loader);
-        }
+    ///
+    /// The font loader is owned by the current thread and receives all font
+    /// loader callbacks issued on it.
+    pub fn set_font_loader<F: FontLoader + 'static>(font_loader: F) {
+        font_loader::set_active_font_loader(font_loader);
+        Self::set_font_loader_raw(font_loader::raw_font_loader());
     }
 
-    /// Set a custom surface definition.
-    pub fn set_surface_definition(surface_definition: ULSurfaceDefinition) {
+    /// Set a custom font loader from raw callbacks.
+    pub fn set_font_loader_raw(font_loader: ULFontLoader) {
         unsafe {
-            ulPlatformSetSurfaceDefinition(surface_definition);
+            ulPlatformSetFontLoader(font_loader);
         }
     }
 
-    /// Set a custom GPU driver implementation.
-    pub fn set_gpu_driver(gpu_driver: ULGPUDriver) {
-        unsafe {
-            ulPlatformSetGPUDriver(gpu_driver);
-        }
+    /// Set a custom surface definition implementation.
+    ///
+    /// This must be called before creating the renderer; surfaces that already
+    /// exist keep the definition they were created with.
+    pub fn set_surface_definition<S: SurfaceDefinition + 'static>() {
+        Self::set_surface_definition_raw(S::to_raw());
     }
 
-    /// Set a custom clipboard implementation.
-    pub fn set_clipboard(clipboard: ULClipboard) {
-        unsafe {
-            ulPlatformSetClipboard(clipboard);
-        }
+    /// Set a custom surface implemented in safe Rust, such as `VecSurface`.
+    ///
+    /// This must be called before creating the renderer; surfaces that already
+    /// exist keep the definition they were created with.
+    pub fn set_custom_surface<S: CustomSurface + 'static>() {
+        Self::set_surface_definition_raw(S::to_raw());
     }
 
-    /// Enable the default logger (requires AppCore).
-    pub fn enable_default_logger(log_path: &str) {
-        let log_path_string = String::from_str(log_path);
+    /// Set a custom surface definition from raw callbacks.
+    pub fn set_surface_definition_raw(surface_definition: ULSurfaceDefinition) {
+        surface::set_custom_surface_definition();
         unsafe {
-            ulEnableDefaultLogger(log_path_string.raw());
+            ulPlatformSetSurfaceDefinition(surface_definition);
         }
     }
 
-    /// Enable the platform file system (requires AppCore).
-    pub fn enable_platform_file_system(base_dir: &str) {
-        unsafe extern "C" {
-            fn ulEnablePlatformFileSystem(base_dir: ULString);
-        }
+    /// Set a custom GPU driver implementation.
+    ///
+    /// The driver is owned by the current thread and receives all GPU callbacks
+    /// issued while rendering on it.
+    pub fn set_gpu_driver<D: GpuDriver + 'static>(driver: D) {
+        gpu_driver::set_active_driver(driver);
+        Self::set_gpu_driver_raw(gpu_driver::raw_driver());
+    }
 
-        let base_dir_str = String::from_str(base_dir);
+    /// Set a custom GPU driver from raw callbacks.
+    pub fn set_gpu_driver_raw(gpu_driver: ULGPUDriver) {
         unsafe {
-            ulEnablePlatformFileSystem(base_dir_str.raw());
+            ulPlatformSetGPUDriver(gpu_driver);
         }
     }
 
-    /// Enable the platform font loader (requires AppCore).
-    pub fn enable_platform_font_loader() {
-        unsafe extern "C" {
-            fn ulEnablePlatformFontLoader();
-        }
+    /// Set a custom clipboard implementation.
+    ///
+    /// The clipboard is owned by the current thread and receives all clipboard
+    /// callbacks issued on it.
+    pub fn set_clipboard<C: Clipboard + 'static>(clipboard: C) {
+        clipboard::set_active_clipboard(clipboard);
+        Self::set_clipboard_raw(clipboard::raw_clipboard());
+    }
 
+    /// Set a custom clipboard from raw callbacks.
+    pub fn set_clipboard_raw(clipboard: ULClipboard) {
         unsafe {
-            ulEnablePlatformFontLoader();
+            ulPlatformSetClipboard(clipboard);
         }
     }
 }
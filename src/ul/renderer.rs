@@ -1,14 +1,86 @@
-use crate::ul::config::Config;
-use crate::ul::events::{GamepadAxisEvent, GamepadButtonEvent, GamepadEvent};
+use crate::ul::config::{self, Config};
+use crate::ul::error::Error;
+use crate::ul::events::{GamepadAxisEvent, GamepadButtonEvent, GamepadEvent, GamepadEventType};
 use crate::ul::ffi::{
     ULRenderer, ulCreateRenderer, ulDestroyRenderer, ulFireGamepadAxisEvent,
     ulFireGamepadButtonEvent, ulFireGamepadEvent, ulLogMemoryUsage, ulPurgeMemory,
     ulRefreshDisplay, ulRender, ulSetGamepadDetails, ulStartRemoteInspectorServer, ulUpdate,
 };
+use crate::ul::logger;
+use crate::ul::platform;
 use crate::ul::session::Session;
 use crate::ul::string::String;
 use std::ffi::CString;
 
+/// A single figure from the renderer's memory usage report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryUsageEntry {
+    /// The label of the figure, as logged by Ultralight.
+    pub name: std::string::String,
+    /// The reported size in bytes.
+    pub bytes: u64,
+}
+
+/// Memory usage statistics parsed from the renderer's memory usage log.
+///
+/// Ultralight has no structured memory query, so this is parsed from the report
+/// written by `log_memory_usage`. Only lines of the form `name: <size> <unit>` are
+/// turned into entries; the full report is kept in `report`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryUsage {
+    /// The reported total, or the sum of all entries if no total was reported.
+    pub total_bytes: u64,
+    /// The figures found in the report, in order.
+    pub entries: Vec<MemoryUsageEntry>,
+    /// The raw lines of the report.
+    pub report: Vec<std::string::String>,
+}
+
+impl MemoryUsage {
+    fn parse(messages: Vec<std::string::String>) -> Self {
+        let report: Vec<std::string::String> = messages
+            .iter()
+            .flat_map(|message| message.lines())
+            .map(str::to_owned)
+            .collect();
+        let entries: Vec<MemoryUsageEntry> = report
+            .iter()
+            .filter_map(|line| {
+                let (name, size) = line.split_once(':')?;
+                Some(MemoryUsageEntry {
+                    name: name.trim().to_owned(),
+                    bytes: parse_byte_size(size)?,
+                })
+            })
+            .collect();
+        let total_bytes = entries
+            .iter()
+            .find(|entry| entry.name.to_ascii_lowercase().contains("total"))
+            .map(|entry| entry.bytes)
+            .unwrap_or_else(|| entries.iter().map(|entry| entry.bytes).sum());
+
+        Self {
+            total_bytes,
+            entries,
+            report,
+        }
+    }
+}
+
+/// Parse a size such as "12.5 MB" or "512 bytes" into bytes.
+fn parse_byte_size(size: &str) -> Option<u64> {
+    let mut tokens = size.split_whitespace();
+    let value: f64 = tokens.next()?.parse().ok()?;
+    let scale = match tokens.next()?.to_ascii_uppercase().as_str() {
+        "B" | "BYTES" => 1.0,
+        "KB" | "KIB" => 1024.0,
+        "MB" | "MIB" => 1024.0 * 1024.0,
+        "GB" | "GIB" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((value * scale) as u64)
+}
+
 /// A safe wrapper around Ultralight's ULRenderer type.
 pub struct Renderer {
     raw: ULRenderer,
@@ -24,6 +96,29 @@ impl Renderer {
         }
     }
 
+    /// Create a new renderer, checking the configuration first.
+    ///
+    /// If the platform file system was enabled with
+    /// `app_core::platform::enable_platform_file_system` and resource validation is
+    /// enabled in the config, this checks that the resources folder exists and
+    /// contains the files Ultralight needs, instead of letting the renderer fail later.
+    pub fn try_new(config: Config) -> Result<Self, Error> {
+        if config.validates_resources()
+            && let Some(base_dir) = platform::file_system_base_dir()
+        {
+            config::check_resources(&base_dir.join(config.resource_path_prefix()))?;
+        }
+
+        let renderer = Self::new(config);
+        if renderer.raw.is_null() {
+            return Err(Error::CreationFailed(
+                "failed to create renderer".to_owned(),
+            ));
+        }
+
+        Ok(renderer)
+    }
+
     /// Create a renderer from a raw ULRenderer pointer.
     ///
     /// # Safety
@@ -73,12 +168,45 @@ impl Renderer {
         }
     }
 
+    /// Get memory usage statistics for the renderer.
+    ///
+    /// This captures the report written by `log_memory_usage` by briefly installing
+    /// the crate's logger dispatch. A logger set with `Platform::set_rust_logger`
+    /// still receives the report. A logger set with `Platform::set_logger` or
+    /// `app_core::platform::enable_default_logger` misses it and is restored
+    /// afterwards.
+    pub fn memory_usage(&self) -> Result<MemoryUsage, Error> {
+        let messages = logger::capture(|| self.log_memory_usage());
+        if messages.is_empty() {
+            return Err(Error::UltralightError("Memory usage report was empty"));
+        }
+
+        Ok(MemoryUsage::parse(messages))
+    }
+
     /// Start the remote inspector server.
     pub fn start_remote_inspector_server(&self, address: &str, port: u16) -> bool {
         let c_address = CString::new(address).unwrap();
         unsafe { ulStartRemoteInspectorServer(self.raw, c_address.as_ptr(), port) }
     }
 
+    /// Start the remote inspector server, returning an error if it could not be started.
+    ///
+    /// Only pages whose JavaScript context is inspectable (see
+    /// `GlobalContext::set_inspectable`) are listed by the inspector.
+    pub fn start_inspector_server(&self, address: &str, port: u16) -> Result<(), Error> {
+        let c_address = CString::new(address)
+            .map_err(|_| Error::InvalidArgument("Address contains a null byte"))?;
+        if unsafe { ulStartRemoteInspectorServer(self.raw, c_address.as_ptr(), port) } {
+            Ok(())
+        } else {
+            Err(Error::UltralightError(
+                "Failed to start the inspector server (address or port may be in use); \
+                 note that pages must have an inspectable context, see GlobalContext::set_inspectable",
+            ))
+        }
+    }
+
     /// Describe the details of a gamepad.
     pub fn set_gamepad_details(&self, index: u32, id: &str, axis_count: u32, button_count: u32) {
         let id_str = String::from_str(id);
@@ -108,6 +236,26 @@ impl Renderer {
         }
     }
 
+    /// Describe a gamepad and fire the event that connects it.
+    ///
+    /// Pages see the gamepad in `navigator.getGamepads()` once the renderer has
+    /// been updated.
+    pub fn connect_gamepad(&self, index: u32, id: &str, axis_count: u32, button_count: u32) {
+        self.set_gamepad_details(index, id, axis_count, button_count);
+        self.fire_gamepad_event(&GamepadEvent::new(
+            index,
+            GamepadEventType::kGamepadEventType_Connected,
+        ));
+    }
+
+    /// Fire the event that disconnects a gamepad.
+    pub fn disconnect_gamepad(&self, index: u32) {
+        self.fire_gamepad_event(&GamepadEvent::new(
+            index,
+            GamepadEventType::kGamepadEventType_Disconnected,
+        ));
+    }
+
     /// Create a new session.
     pub fn create_session(&self, is_persistent: bool, name: &str) -> Session {
         Session::new(self, is_persistent, name)
@@ -128,3 +276,59 @@ impl Drop for Renderer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_byte_size_reads_units() {
+        assert_eq!(parse_byte_size(" 512 bytes"), Some(512));
+        assert_eq!(parse_byte_size("1 B"), Some(1));
+        assert_eq!(parse_byte_size("2 KB"), Some(2048));
+        assert_eq!(parse_byte_size("1.5 MiB"), Some(1536 * 1024));
+        assert_eq!(parse_byte_size("1 gb"), Some(1024 * 1024 * 1024));
+    }
+
+    #[test]
+    fn parse_byte_size_rejects_other_text() {
+        assert_eq!(parse_byte_size(""), None);
+        assert_eq!(parse_byte_size("12"), None);
+        assert_eq!(parse_byte_size("many MB"), None);
+        assert_eq!(parse_byte_size("3 parsecs"), None);
+    }
+
+    #[test]
+    fn memory_usage_uses_reported_total() {
+        let usage = MemoryUsage::parse(vec![
+            "Memory usage report\nJS heap: 2 MB\nImage cache: 512 KB".to_owned(),
+            "Total: 3 MB".to_owned(),
+        ]);
+
+        assert_eq!(usage.report.len(), 4);
+        assert_eq!(
+            usage.entries,
+            [
+                MemoryUsageEntry {
+                    name: "JS heap".to_owned(),
+                    bytes: 2 * 1024 * 1024,
+                },
+                MemoryUsageEntry {
+                    name: "Image cache".to_owned(),
+                    bytes: 512 * 1024,
+                },
+                MemoryUsageEntry {
+                    name: "Total".to_owned(),
+                    bytes: 3 * 1024 * 1024,
+                },
+            ]
+        );
+        assert_eq!(usage.total_bytes, 3 * 1024 * 1024);
+    }
+
+    #[test]
+    fn memory_usage_sums_entries_without_total() {
+        let usage = MemoryUsage::parse(vec!["a: 1 KB".to_owned(), "b: 3 KB".to_owned()]);
+        assert_eq!(usage.total_bytes, 4 * 1024);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::ul::bitmap::Bitmap;
+use crate::ul::config::Config;
+use crate::ul::error::Error;
+use crate::ul::renderer::Renderer;
+use crate::ul::view::{LoadState, View};
+use crate::ul::view_config::ViewConfig;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Maximum time to wait for a page to finish loading.
+const LOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Interval between renderer updates while waiting for a page to load.
+const UPDATE_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Render an HTML string to a PNG file.
+///
+/// This creates its own renderer, so it must not be called while another Renderer
+/// exists. The platform font loader and file system must be set up beforehand.
+pub fn render_html_to_png(html: &str, width: u32, height: u32, path: &Path) -> Result<(), Error> {
+    let path = png_path(path)?;
+    let bitmap = render_html_to_bitmap(html, width, height)?;
+    write_png(&bitmap, path)
+}
+
+/// Render a URL to a PNG file.
+///
+/// This has the same requirements as [`render_html_to_png`].
+pub fn render_url_to_png(url: &str, width: u32, height: u32, path: &Path) -> Result<(), Error> {
+    let path = png_path(path)?;
+    let bitmap = render_to_bitmap(width, height, |view| view.load_url(url))?;
+    write_png(&bitmap, path)
+}
+
+/// Render an HTML string to a BGRA bitmap.
+///
+/// This has the same requirements as [`render_html_to_png`]. The bitmap is a copy,
+/// so it outlives the renderer used to draw it.
+pub fn render_html_to_bitmap(html: &str, width: u32, height: u32) -> Result<Bitmap, Error> {
+    render_to_bitmap(width, height, |view| view.load_html(html))
+}
+
+fn png_path(path: &Path) -> Result<&str, Error> {
+    path.to_str()
+        .ok_or(Error::InvalidArgument("PNG path is not valid UTF-8"))
+}
+
+fn write_png(bitmap: &Bitmap, path: &str) -> Result<(), Error> {
+    if !bitmap.write_png(path) {
+        return Err(Error::UltralightError("Failed to write PNG"));
+    }
+
+    Ok(())
+}
+
+fn render_to_bitmap(width: u32, height: u32, load: impl FnOnce(&View)) -> Result<Bitmap, Error> {
+    let renderer = Renderer::try_new(Config::new())?;
+    let view_config = ViewConfig::builder().accelerated(false).build();
+    let view = View::new(&renderer, width, height, &view_config, None);
+    load(&view);
+
+    // Wait for the main frame to finish loading; `is_loading` is still false
+    // before the load has started, which would capture a blank page.
+    let deadline = Instant::now() + LOAD_TIMEOUT;
+    loop {
+        renderer.update();
+        match view.load_state() {
+            LoadState::Loaded => break,
+            LoadState::Failed => {
+                return Err(Error::InvalidOperation("Failed to load the page"));
+            }
+            LoadState::NotStarted | LoadState::Loading => {}
+        }
+        if Instant::now() >= deadline {
+            return Err(Error::InvalidOperation(
+                "Timed out waiting for the page to load",
+            ));
+        }
+        thread::sleep(UPDATE_INTERVAL);
+    }
+
+    renderer.refresh_display(view.display_id());
+    renderer.render();
+
+    let surface = view
+        .surface()
+        .ok_or(Error::NullReference("View has no surface"))?;
+    let bitmap_surface = surface.as_bitmap_surface().ok_or(Error::InvalidOperation(
+        "View surface is not a bitmap surface",
+    ))?;
+
+    Ok(Bitmap::from_copy(&bitmap_surface.bitmap()))
+}
@@ -1,3 +1,4 @@
+use crate::ul::error::Error;
 use crate::ul::ffi::{
     ULSession, ulCreateSession, ulDefaultSession, ulDestroySession, ulSessionGetDiskPath,
     ulSessionGetId, ulSessionGetName, ulSessionIsPersistent,
@@ -21,6 +22,35 @@ impl Session {
         }
     }
 
+    /// Create a persistent session backed by a directory on disk.
+    ///
+    /// Returns an error if the name contains null bytes or the session's disk
+    /// path cannot be created.
+    pub fn persistent(renderer: &Renderer, name: &str) -> Result<Self, Error> {
+        let name_str = String::try_from_str(name)?;
+        let session = unsafe {
+            let raw = ulCreateSession(renderer.raw(), true, name_str.raw());
+            if raw.is_null() {
+                return Err(Error::UltralightError("Failed to create session"));
+            }
+            Self { raw, owned: true }
+        };
+
+        let disk_path = session.disk_path();
+        let disk_path = disk_path.as_str()?;
+        if disk_path.is_empty() {
+            return Err(Error::UltralightError("Session has no disk path"));
+        }
+        std::fs::create_dir_all(disk_path).map_err(Error::IoError)?;
+
+        Ok(session)
+    }
+
+    /// Create a session that only stores data in memory.
+    pub fn in_memory(renderer: &Renderer, name: &str) -> Self {
+        Self::new(renderer, false, name)
+    }
+
     /// Get the default session.
     pub fn default(renderer: &Renderer) -> Self {
         unsafe {
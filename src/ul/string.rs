@@ -8,6 +8,8 @@ use std::ffi::{CStr, CString};
 use std::fmt;
 use std::ops::Deref;
 use std::os::raw::c_char;
+use std::slice;
+use std::str::{CharIndices, Chars};
 
 /// A safe wrapper around Ultralight's ULString type.
 pub struct String {
@@ -45,7 +47,8 @@ impl String {
     ///
     /// # Panics
     ///
-    /// Panics if the input string contains null bytes.
+    /// Panics if the input string contains null bytes. Use `try_from_str` to
+    /// handle that case without panicking.
     pub fn from_str(s: &str) -> Self {
         let c_str = CString::new(s).unwrap();
         unsafe {
@@ -54,6 +57,18 @@ impl String {
         }
     }
 
+    /// Create a new string from a Rust string slice, failing on null bytes.
+    ///
+    /// Use this instead of `from_str` for untrusted input.
+    pub fn try_from_str(s: &str) -> Result<Self, Error> {
+        let c_str =
+            CString::new(s).map_err(|_| Error::InvalidArgument("String contains null bytes"))?;
+        unsafe {
+            let raw = ulCreateString(c_str.as_ptr());
+            Ok(Self { raw, owned: true })
+        }
+    }
+
     /// Create a new string from UTF-8 data.
     ///
     /// This function creates a new string from UTF-8 data. The resulting string
@@ -123,10 +138,54 @@ impl String {
         }
     }
 
+    /// Get the raw UTF-8 bytes of the string without validating them.
+    ///
+    /// This function returns the string data as a byte slice, not including the null
+    /// terminator. Unlike `as_str`, it does not check that the data is valid UTF-8.
+    /// If the string or its data is null, an empty slice is returned.
+    pub fn as_bytes(&self) -> &[u8] {
+        if self.raw.is_null() {
+            return &[];
+        }
+
+        unsafe {
+            let data = ulStringGetData(self.raw);
+            if data.is_null() {
+                return &[];
+            }
+
+            slice::from_raw_parts(data as *const u8, ulStringGetLength(self.raw))
+        }
+    }
+
+    /// Get an iterator over the characters of the string.
+    ///
+    /// This function fails under the same conditions as `as_str`.
+    pub fn chars(&self) -> Result<Chars<'_>, Error> {
+        self.as_str().map(str::chars)
+    }
+
+    /// Get an iterator over the characters of the string and their byte offsets.
+    ///
+    /// This function fails under the same conditions as `as_str`.
+    pub fn char_indices(&self) -> Result<CharIndices<'_>, Error> {
+        self.as_str().map(str::char_indices)
+    }
+
+    /// Get the length of the string in UTF-16 code units.
+    ///
+    /// Ultralight stores strings as UTF-8, so this is computed by re-encoding the
+    /// data. It matches the `length` JavaScript reports for the same text.
+    /// This function fails under the same conditions as `as_str`.
+    pub fn utf16_len(&self) -> Result<usize, Error> {
+        self.as_str().map(|s| s.encode_utf16().count())
+    }
+
     /// Get the length of the string in bytes.
     ///
-    /// This function returns the length of the string in bytes, not including the null terminator.
-    /// If the string is null, 0 is returned.
+    /// This function returns the length of the UTF-8 data in bytes, not including the
+    /// null terminator. This is not the number of characters; use `chars` or `utf16_len`
+    /// for those. If the string is null, 0 is returned.
     pub fn len(&self) -> usize {
         if self.raw.is_null() {
             return 0;
@@ -9,6 +9,74 @@ use crate::ul::geometry::IntRect;
 use std::marker::PhantomData;
 use std::os::raw::c_void;
 use std::slice;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether a custom surface definition replaced the default bitmap surfaces.
+static CUSTOM_SURFACE_DEFINITION: AtomicBool = AtomicBool::new(false);
+
+/// Record that a custom surface definition is installed, so surfaces are no longer
+/// bitmap surfaces.
+pub(crate) fn set_custom_surface_definition() {
+    CUSTOM_SURFACE_DEFINITION.store(true, Ordering::Relaxed);
+}
+
+/// A rectangular block of BGRA8 pixels copied out of a surface.
+///
+/// Rows are tightly packed, so the stride is `width() * 4` bytes.
+#[derive(Debug, Clone)]
+pub struct Image {
+    /// The region of the surface the pixels were copied from.
+    pub bounds: IntRect,
+    /// The pixel data.
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Get the width in pixels.
+    pub fn width(&self) -> u32 {
+        self.bounds.width() as u32
+    }
+
+    /// Get the height in pixels.
+    pub fn height(&self) -> u32 {
+        self.bounds.height() as u32
+    }
+}
+
+/// The outcome of [`Surface::resize_checked`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResizeInfo {
+    /// The new width in pixels.
+    pub width: u32,
+    /// The new height in pixels.
+    pub height: u32,
+    /// The new number of bytes per row.
+    pub row_bytes: u32,
+    /// Whether the number of bytes per row changed, invalidating cached strides.
+    pub row_bytes_changed: bool,
+    /// Whether the size of the pixel buffer changed, which usually means it was reallocated.
+    pub size_changed: bool,
+}
+
+impl ResizeInfo {
+    /// Describe a resize from the row bytes and buffer size before it and the
+    /// layout after it.
+    fn compare(
+        (old_row_bytes, old_size): (u32, usize),
+        width: u32,
+        height: u32,
+        row_bytes: u32,
+        size: usize,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            row_bytes,
+            row_bytes_changed: row_bytes != old_row_bytes,
+            size_changed: size != old_size,
+        }
+    }
+}
 
 /// A locked surface pixels wrapper that automatically unlocks the pixels when dropped.
 pub struct LockedPixels<'a> {
@@ -39,8 +107,12 @@ impl Drop for LockedPixels<'_> {
 }
 
 /// A safe wrapper around Ultralight's ULSurface type.
+///
+/// Surfaces are owned by a view and share its thread affinity, so they are
+/// neither `Send` nor `Sync`.
 pub struct Surface {
     raw: ULSurface,
+    _marker: PhantomData<*const ()>,
 }
 
 /// A safe wrapper for bitmap surfaces.
@@ -141,6 +213,181 @@ pub trait SurfaceDefinition {
     }
 }
 
+/// Trait for implementing a custom surface in safe Rust.
+///
+/// Unlike [`SurfaceDefinition`], each surface Ultralight creates is an instance of
+/// the implementing type, so the raw user data pointers stay inside the crate.
+pub trait CustomSurface {
+    /// Create a new surface with the specified dimensions.
+    fn new(width: u32, height: u32) -> Self
+    where
+        Self: Sized;
+
+    /// Get the width of the surface in pixels.
+    fn width(&self) -> u32;
+
+    /// Get the height of the surface in pixels.
+    fn height(&self) -> u32;
+
+    /// Get the number of bytes per row.
+    fn row_bytes(&self) -> u32;
+
+    /// Get the size of the pixel buffer in bytes.
+    fn size(&self) -> usize {
+        self.row_bytes() as usize * self.height() as usize
+    }
+
+    /// Lock the pixel buffer for reading/writing.
+    ///
+    /// The returned slice must be at least [`size`](Self::size) bytes long.
+    fn lock_pixels(&mut self) -> &mut [u8];
+
+    /// Unlock the pixel buffer.
+    fn unlock_pixels(&mut self) {}
+
+    /// Resize the pixel buffer.
+    fn resize(&mut self, width: u32, height: u32);
+
+    /// Convert the trait to a raw ULSurfaceDefinition.
+    fn to_raw() -> ULSurfaceDefinition
+    where
+        Self: Sized + 'static,
+    {
+        /// Get the surface behind a user data pointer returned by `create_callback`.
+        ///
+        /// # Safety
+        ///
+        /// The pointer must have been returned by `create_callback::<T>` and not yet
+        /// passed to `destroy_callback::<T>`.
+        unsafe fn surface<'a, T>(user_data: *mut c_void) -> &'a mut T {
+            unsafe { &mut *(user_data as *mut T) }
+        }
+
+        extern "C" fn create_callback<T: CustomSurface>(width: u32, height: u32) -> *mut c_void {
+            Box::into_raw(Box::new(T::new(width, height))) as *mut c_void
+        }
+
+        extern "C" fn destroy_callback<T: CustomSurface>(user_data: *mut c_void) {
+            if !user_data.is_null() {
+                drop(unsafe { Box::from_raw(user_data as *mut T) });
+            }
+        }
+
+        extern "C" fn get_width_callback<T: CustomSurface>(user_data: *mut c_void) -> u32 {
+            unsafe { surface::<T>(user_data).width() }
+        }
+
+        extern "C" fn get_height_callback<T: CustomSurface>(user_data: *mut c_void) -> u32 {
+            unsafe { surface::<T>(user_data).height() }
+        }
+
+        extern "C" fn get_row_bytes_callback<T: CustomSurface>(user_data: *mut c_void) -> u32 {
+            unsafe { surface::<T>(user_data).row_bytes() }
+        }
+
+        extern "C" fn get_size_callback<T: CustomSurface>(user_data: *mut c_void) -> usize {
+            unsafe { surface::<T>(user_data).size() }
+        }
+
+        extern "C" fn lock_pixels_callback<T: CustomSurface>(
+            user_data: *mut c_void,
+        ) -> *mut c_void {
+            unsafe { surface::<T>(user_data).lock_pixels().as_mut_ptr() as *mut c_void }
+        }
+
+        extern "C" fn unlock_pixels_callback<T: CustomSurface>(user_data: *mut c_void) {
+            unsafe { surface::<T>(user_data).unlock_pixels() }
+        }
+
+        extern "C" fn resize_callback<T: CustomSurface>(
+            user_data: *mut c_void,
+            width: u32,
+            height: u32,
+        ) {
+            unsafe { surface::<T>(user_data).resize(width, height) }
+        }
+
+        ULSurfaceDefinition {
+            create: create_callback::<Self>,
+            destroy: destroy_callback::<Self>,
+            get_width: get_width_callback::<Self>,
+            get_height: get_height_callback::<Self>,
+            get_row_bytes: get_row_bytes_callback::<Self>,
+            get_size: get_size_callback::<Self>,
+            lock_pixels: lock_pixels_callback::<Self>,
+            unlock_pixels: unlock_pixels_callback::<Self>,
+            resize: resize_callback::<Self>,
+        }
+    }
+}
+
+/// A ready-made CPU surface backed by a Rust `Vec<u8>`.
+///
+/// Install it with `Platform::set_custom_surface::<VecSurface>()` before creating
+/// the renderer. Views then paint BGRA8 pixels into a tightly packed buffer that
+/// can be read back with [`VecSurface::pixels`].
+pub struct VecSurface {
+    buffer: Vec<u8>,
+    width: u32,
+    height: u32,
+    row_bytes: u32,
+}
+
+impl VecSurface {
+    /// Get the pixel buffer of a surface created by this definition.
+    ///
+    /// # Safety
+    ///
+    /// The surface must have been created while `VecSurface` was the active
+    /// custom surface.
+    pub unsafe fn pixels(surface: &Surface) -> &[u8] {
+        let user_data = surface.user_data();
+        if user_data.is_null() {
+            return &[];
+        }
+
+        unsafe { &(*(user_data as *const Self)).buffer }
+    }
+}
+
+impl CustomSurface for VecSurface {
+    fn new(width: u32, height: u32) -> Self {
+        let row_bytes = width * 4;
+        Self {
+            buffer: vec![0; row_bytes as usize * height as usize],
+            width,
+            height,
+            row_bytes,
+        }
+    }
+
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn row_bytes(&self) -> u32 {
+        self.row_bytes
+    }
+
+    fn size(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn lock_pixels(&mut self) -> &mut [u8] {
+        &mut self.buffer
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        if self.width != width || self.height != height {
+            *self = Self::new(width, height);
+        }
+    }
+}
+
 impl Surface {
     /// Create a new surface from a raw ULSurface pointer.
     ///
@@ -148,7 +395,10 @@ impl Surface {
     ///
     /// The pointer must be a valid ULSurface created by the Ultralight API.
     pub unsafe fn from_raw(raw: ULSurface) -> Self {
-        Self { raw }
+        Self {
+            raw,
+            _marker: PhantomData,
+        }
     }
 
     /// Get a reference to the raw ULSurface.
@@ -200,6 +450,25 @@ impl Surface {
         }
     }
 
+    /// Resize the surface and report whether its buffer layout changed.
+    ///
+    /// Resizing may reallocate the pixel buffer, invalidating any pointer obtained
+    /// from a previous lock. This takes `&mut self` so that the borrow checker
+    /// rejects calls while a `LockedPixels` for this surface is still alive.
+    pub fn resize_checked(&mut self, width: u32, height: u32) -> ResizeInfo {
+        let old_row_bytes = self.row_bytes();
+        let old_size = self.size();
+        self.resize(width, height);
+
+        ResizeInfo::compare(
+            (old_row_bytes, old_size),
+            self.width(),
+            self.height(),
+            self.row_bytes(),
+            self.size(),
+        )
+    }
+
     /// Set the dirty bounds of the surface.
     pub fn set_dirty_bounds(&self, bounds: IntRect) {
         unsafe {
@@ -215,6 +484,16 @@ impl Surface {
         }
     }
 
+    /// Get the dirty bounds of the surface, or `None` if nothing is dirty.
+    pub fn dirty_region(&self) -> Option<IntRect> {
+        let bounds = self.dirty_bounds();
+        if bounds.is_empty() {
+            None
+        } else {
+            Some(bounds)
+        }
+    }
+
     /// Clear the dirty bounds of the surface.
     pub fn clear_dirty_bounds(&self) {
         unsafe {
@@ -222,20 +501,88 @@ impl Surface {
         }
     }
 
+    /// Copy the dirty region of the surface and clear the dirty bounds.
+    ///
+    /// Returns `None` if nothing is dirty or the pixels cannot be locked. Pair
+    /// with `View::set_needs_paint` to force a repaint of the whole view.
+    pub fn read_dirty_region(&self) -> Option<Image> {
+        let dirty = self.dirty_region()?;
+
+        // Clamp to the surface in case the bounds extend past it
+        let bounds = IntRect {
+            left: dirty.left.max(0),
+            top: dirty.top.max(0),
+            right: dirty.right.min(self.width() as i32),
+            bottom: dirty.bottom.min(self.height() as i32),
+        };
+        if bounds.width() <= 0 || bounds.height() <= 0 {
+            self.clear_dirty_bounds();
+            return None;
+        }
+
+        let image = self.copy_region(bounds)?;
+        self.clear_dirty_bounds();
+        Some(image)
+    }
+
+    /// Copy the whole surface.
+    ///
+    /// Returns `None` if the surface is empty or the pixels cannot be locked.
+    pub fn read_pixels(&self) -> Option<Image> {
+        let bounds = IntRect {
+            left: 0,
+            top: 0,
+            right: self.width() as i32,
+            bottom: self.height() as i32,
+        };
+        if bounds.width() <= 0 || bounds.height() <= 0 {
+            return None;
+        }
+
+        self.copy_region(bounds)
+    }
+
+    /// Copy a region that lies within the surface into a tightly packed image.
+    fn copy_region(&self, bounds: IntRect) -> Option<Image> {
+        let row_bytes = self.row_bytes() as usize;
+        let row_len = bounds.width() as usize * 4;
+        let mut pixels = Vec::with_capacity(row_len * bounds.height() as usize);
+
+        {
+            let locked = self.lock_pixels().ok()?;
+            let data = locked.as_slice();
+            for y in bounds.top..bounds.bottom {
+                let start = y as usize * row_bytes + bounds.left as usize * 4;
+                pixels.extend_from_slice(&data[start..start + row_len]);
+            }
+        }
+
+        Some(Image { bounds, pixels })
+    }
+
     /// Get the user data pointer for custom surface implementations.
     pub fn user_data(&self) -> *mut c_void {
         unsafe { ulSurfaceGetUserData(self.raw) }
     }
 
     /// Try to cast this surface to a BitmapSurface.
+    ///
+    /// Returns `None` once a custom surface definition has been installed with
+    /// `Platform::set_surface_definition`, since surfaces are then created by it, or if
+    /// the surface has no bitmap.
     pub fn as_bitmap_surface(&self) -> Option<BitmapSurface> {
-        if self.user_data().is_null() {
-            Some(BitmapSurface {
-                surface: Surface { raw: self.raw },
-            })
-        } else {
-            None
+        if CUSTOM_SURFACE_DEFINITION.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let bitmap = unsafe { ulBitmapSurfaceGetBitmap(self.raw) };
+        if bitmap.is_null() {
+            return None;
         }
+
+        Some(BitmapSurface {
+            surface: unsafe { Surface::from_raw(self.raw) },
+        })
     }
 }
 
@@ -247,7 +594,7 @@ impl BitmapSurface {
     /// The pointer must be a valid ULBitmapSurface created by the Ultralight API.
     pub unsafe fn from_raw(raw: ULBitmapSurface) -> Self {
         Self {
-            surface: Surface { raw },
+            surface: unsafe { Surface::from_raw(raw) },
         }
     }
 
@@ -269,3 +616,62 @@ impl BitmapSurface {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resize_info_reports_layout_changes() {
+        let same = ResizeInfo::compare((400, 40_000), 100, 100, 400, 40_000);
+        assert!(!same.row_bytes_changed && !same.size_changed);
+
+        let taller = ResizeInfo::compare((400, 40_000), 100, 200, 400, 80_000);
+        assert!(!taller.row_bytes_changed && taller.size_changed);
+
+        let wider = ResizeInfo::compare((400, 40_000), 200, 50, 800, 40_000);
+        assert!(wider.row_bytes_changed && !wider.size_changed);
+        assert_eq!((wider.width, wider.height, wider.row_bytes), (200, 50, 800));
+    }
+
+    #[test]
+    fn vec_surface_resizes_its_buffer() {
+        let mut surface = VecSurface::new(4, 2);
+        assert_eq!(surface.row_bytes(), 16);
+        assert_eq!(surface.size(), 32);
+
+        surface.lock_pixels()[0] = 0xff;
+        surface.resize(4, 2);
+        assert_eq!(surface.lock_pixels()[0], 0xff);
+
+        surface.resize(3, 5);
+        assert_eq!(
+            (surface.width(), surface.height(), surface.row_bytes()),
+            (3, 5, 12)
+        );
+        assert_eq!(surface.lock_pixels().len(), 60);
+        assert_eq!(surface.lock_pixels()[0], 0);
+    }
+
+    #[test]
+    fn custom_surface_callbacks_round_trip() {
+        let definition = VecSurface::to_raw();
+        let user_data = (definition.create)(2, 3);
+        assert_eq!((definition.get_width)(user_data), 2);
+        assert_eq!((definition.get_height)(user_data), 3);
+        assert_eq!((definition.get_row_bytes)(user_data), 8);
+        assert_eq!((definition.get_size)(user_data), 24);
+
+        let pixels = (definition.lock_pixels)(user_data) as *mut u8;
+        unsafe { *pixels.add(23) = 7 };
+        (definition.unlock_pixels)(user_data);
+        assert_eq!(
+            unsafe { *((definition.lock_pixels)(user_data) as *mut u8).add(23) },
+            7
+        );
+
+        (definition.resize)(user_data, 1, 1);
+        assert_eq!((definition.get_size)(user_data), 4);
+        (definition.destroy)(user_data);
+    }
+}
@@ -1,5 +1,6 @@
+use crate::javascript_core::{Context, Object};
 use crate::ul::error::Error;
-use crate::ul::events::{KeyEvent, MouseEvent, ScrollEvent};
+use crate::ul::events::{KeyEvent, MouseButton, MouseEvent, MouseEventType, ScrollEvent};
 use crate::ul::ffi::{
     JSContextRef, ULCursor, ULIntRect, ULMessageLevel, ULMessageSource, ULRenderTarget, ULString,
     ULView, ulCreateView, ulDestroyView, ulViewCanGoBack, ulViewCanGoForward,
@@ -18,13 +19,22 @@ use crate::ul::ffi::{
     ulViewStop, ulViewUnfocus, ulViewUnlockJSContext,
 };
 use crate::ul::geometry::{IntRect, Rect};
+use crate::ul::platform;
 use crate::ul::renderer::Renderer;
 use crate::ul::session::Session;
 use crate::ul::string::String;
-use crate::ul::surface::Surface;
+use crate::ul::surface::{Image, Surface};
 use crate::ul::view_config::ViewConfig;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
 use std::os::raw::{c_int, c_uint, c_ulonglong, c_void};
+use std::path::{Component, Path, Prefix};
 use std::ptr;
+use std::rc::{Rc, Weak};
+use std::sync::mpsc;
 
 pub use crate::ul::ffi::{
     ULCursor as Cursor, ULMessageLevel as MessageLevel, ULMessageSource as MessageSource,
@@ -59,6 +69,59 @@ impl RenderTarget {
             render_buffer_id: raw.render_buffer_id,
         }
     }
+
+    /// Check if the render target has a texture that can be sampled.
+    pub fn is_ready(&self) -> bool {
+        !self.is_empty && self.texture_id != 0
+    }
+
+    /// Get the region of the texture that holds the view, in texture pixels.
+    ///
+    /// The texture may be larger than the view and shared with other views, so only
+    /// this region (`uv_coords` scaled by the texture dimensions) should be sampled.
+    pub fn texture_rect(&self) -> IntRect {
+        let width = self.texture_width as f32;
+        let height = self.texture_height as f32;
+        IntRect {
+            left: (self.uv_coords.left * width).round() as i32,
+            top: (self.uv_coords.top * height).round() as i32,
+            right: (self.uv_coords.right * width).round() as i32,
+            bottom: (self.uv_coords.bottom * height).round() as i32,
+        }
+    }
+}
+
+/// A message added to the page's console.
+#[derive(Debug, Clone)]
+pub struct ConsoleMessage {
+    pub source: MessageSource,
+    pub level: MessageLevel,
+    pub text: std::string::String,
+    pub line: u32,
+    pub column: u32,
+    pub source_id: std::string::String,
+}
+
+impl fmt::Display for ConsoleMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.level {
+            MessageLevel::kMessageLevel_Log => "log",
+            MessageLevel::kMessageLevel_Warning => "warning",
+            MessageLevel::kMessageLevel_Error => "error",
+            MessageLevel::kMessageLevel_Debug => "debug",
+            MessageLevel::kMessageLevel_Info => "info",
+        };
+
+        if self.source_id.is_empty() {
+            write!(f, "[{}] {}", level, self.text)
+        } else {
+            write!(
+                f,
+                "[{}] {} ({}:{}:{})",
+                level, self.text, self.source_id, self.line, self.column
+            )
+        }
+    }
 }
 
 /// Callback for when the page title changes.
@@ -83,16 +146,32 @@ pub trait ChangeCursorCallback: Send {
 
 /// Callback for when a message is added to the console.
 pub trait AddConsoleMessageCallback: Send {
+    fn on_console_message(&self, view: &View, message: &ConsoleMessage) {
+        #[allow(deprecated)]
+        self.on_add_console_message(
+            view,
+            message.source,
+            message.level,
+            &message.text,
+            message.line,
+            message.column,
+            &message.source_id,
+        );
+    }
+
+    #[deprecated(note = "implement `on_console_message` instead")]
+    #[allow(clippy::too_many_arguments)]
     fn on_add_console_message(
         &self,
-        view: &View,
-        source: MessageSource,
-        level: MessageLevel,
-        message: &str,
-        line_number: u32,
-        column_number: u32,
-        source_id: &str,
-    );
+        _view: &View,
+        _source: MessageSource,
+        _level: MessageLevel,
+        _message: &str,
+        _line_number: u32,
+        _column_number: u32,
+        _source_id: &str,
+    ) {
+    }
 }
 
 /// Callback for when a child view needs to be created.
@@ -163,14 +242,11 @@ extern "C" fn change_title_callback<T: ChangeTitleCallback>(
     title: ULString,
 ) {
     unsafe {
-        let callback = &*(user_data as *const T);
-        let view = View::from_raw(caller);
+        let callback = CallbackData::<T>::get(user_data);
+        let view = CallbackData::<T>::view(user_data, caller);
         let title_str = String::from_raw(title, false);
 
         callback.on_change_title(&view, &title_str);
-
-        // Prevent drop of view to avoid deallocation
-        std::mem::forget(view);
     }
 }
 
@@ -180,14 +256,11 @@ extern "C" fn change_url_callback<T: ChangeURLCallback>(
     url: ULString,
 ) {
     unsafe {
-        let callback = &*(user_data as *const T);
-        let view = View::from_raw(caller);
+        let callback = CallbackData::<T>::get(user_data);
+        let view = CallbackData::<T>::view(user_data, caller);
         let url_str = String::from_raw(url, false);
 
         callback.on_change_url(&view, &url_str);
-
-        // Prevent drop of view to avoid deallocation
-        std::mem::forget(view);
     }
 }
 
@@ -197,14 +270,11 @@ extern "C" fn change_tooltip_callback<T: ChangeTooltipCallback>(
     tooltip: ULString,
 ) {
     unsafe {
-        let callback = &*(user_data as *const T);
-        let view = View::from_raw(caller);
+        let callback = CallbackData::<T>::get(user_data);
+        let view = CallbackData::<T>::view(user_data, caller);
         let tooltip_str = String::from_raw(tooltip, false);
 
         callback.on_change_tooltip(&view, &tooltip_str);
-
-        // Prevent drop of view to avoid deallocation
-        std::mem::forget(view);
     }
 }
 
@@ -214,13 +284,10 @@ extern "C" fn change_cursor_callback<T: ChangeCursorCallback>(
     cursor: ULCursor,
 ) {
     unsafe {
-        let callback = &*(user_data as *const T);
-        let view = View::from_raw(caller);
+        let callback = CallbackData::<T>::get(user_data);
+        let view = CallbackData::<T>::view(user_data, caller);
 
         callback.on_change_cursor(&view, cursor);
-
-        // Prevent drop of view to avoid deallocation
-        std::mem::forget(view);
     }
 }
 
@@ -235,23 +302,18 @@ extern "C" fn add_console_message_callback<T: AddConsoleMessageCallback>(
     source_id: ULString,
 ) {
     unsafe {
-        let callback = &*(user_data as *const T);
-        let view = View::from_raw(caller);
-        let message_str = String::from_raw(message, false);
-        let source_id_str = String::from_raw(source_id, false);
-
-        callback.on_add_console_message(
-            &view,
+        let callback = CallbackData::<T>::get(user_data);
+        let view = CallbackData::<T>::view(user_data, caller);
+        let message = ConsoleMessage {
             source,
             level,
-            &message_str,
-            line_number,
-            column_number,
-            &source_id_str,
-        );
+            text: String::from_raw(message, false).to_string(),
+            line: line_number,
+            column: column_number,
+            source_id: String::from_raw(source_id, false).to_string(),
+        };
 
-        // Prevent drop of view to avoid deallocation
-        std::mem::forget(view);
+        callback.on_console_message(&view, &message);
     }
 }
 
@@ -264,8 +326,8 @@ extern "C" fn create_child_view_callback<T: CreateChildViewCallback>(
     popup_rect: ULIntRect,
 ) -> ULView {
     unsafe {
-        let callback = &*(user_data as *const T);
-        let view = View::from_raw(caller);
+        let callback = CallbackData::<T>::get(user_data);
+        let view = CallbackData::<T>::view(user_data, caller);
         let opener_url_str = String::from_raw(opener_url, false);
         let target_url_str = String::from_raw(target_url, false);
         let popup_rect_rust = IntRect::from_raw(popup_rect);
@@ -278,9 +340,6 @@ extern "C" fn create_child_view_callback<T: CreateChildViewCallback>(
             popup_rect_rust,
         );
 
-        // Prevent drop of view to avoid deallocation
-        std::mem::forget(view);
-
         match result {
             Some(child_view) => {
                 let raw = child_view.raw;
@@ -300,15 +359,12 @@ extern "C" fn create_inspector_view_callback<T: CreateInspectorViewCallback>(
     inspected_url: ULString,
 ) -> ULView {
     unsafe {
-        let callback = &*(user_data as *const T);
-        let view = View::from_raw(caller);
+        let callback = CallbackData::<T>::get(user_data);
+        let view = CallbackData::<T>::view(user_data, caller);
         let inspected_url_str = String::from_raw(inspected_url, false);
 
         let result = callback.on_create_inspector_view(&view, is_local, &inspected_url_str);
 
-        // Prevent drop of view to avoid deallocation
-        std::mem::forget(view);
-
         match result {
             Some(inspector_view) => {
                 let raw = inspector_view.raw;
@@ -329,14 +385,11 @@ extern "C" fn begin_loading_callback<T: BeginLoadingCallback>(
     url: ULString,
 ) {
     unsafe {
-        let callback = &*(user_data as *const T);
-        let view = View::from_raw(caller);
+        let callback = CallbackData::<T>::get(user_data);
+        let view = CallbackData::<T>::view(user_data, caller);
         let url_str = String::from_raw(url, false);
 
         callback.on_begin_loading(&view, frame_id, is_main_frame, &url_str);
-
-        // Prevent drop of view to avoid deallocation
-        std::mem::forget(view);
     }
 }
 
@@ -348,14 +401,11 @@ extern "C" fn finish_loading_callback<T: FinishLoadingCallback>(
     url: ULString,
 ) {
     unsafe {
-        let callback = &*(user_data as *const T);
-        let view = View::from_raw(caller);
+        let callback = CallbackData::<T>::get(user_data);
+        let view = CallbackData::<T>::view(user_data, caller);
         let url_str = String::from_raw(url, false);
 
         callback.on_finish_loading(&view, frame_id, is_main_frame, &url_str);
-
-        // Prevent drop of view to avoid deallocation
-        std::mem::forget(view);
     }
 }
 
@@ -370,8 +420,8 @@ extern "C" fn fail_loading_callback<T: FailLoadingCallback>(
     error_code: c_int,
 ) {
     unsafe {
-        let callback = &*(user_data as *const T);
-        let view = View::from_raw(caller);
+        let callback = CallbackData::<T>::get(user_data);
+        let view = CallbackData::<T>::view(user_data, caller);
         let url_str = String::from_raw(url, false);
         let description_str = String::from_raw(description, false);
         let error_domain_str = String::from_raw(error_domain, false);
@@ -385,9 +435,6 @@ extern "C" fn fail_loading_callback<T: FailLoadingCallback>(
             &error_domain_str,
             error_code,
         );
-
-        // Prevent drop of view to avoid deallocation
-        std::mem::forget(view);
     }
 }
 
@@ -399,14 +446,11 @@ extern "C" fn window_object_ready_callback<T: WindowObjectReadyCallback>(
     url: ULString,
 ) {
     unsafe {
-        let callback = &*(user_data as *const T);
-        let view = View::from_raw(caller);
+        let callback = CallbackData::<T>::get(user_data);
+        let view = CallbackData::<T>::view(user_data, caller);
         let url_str = String::from_raw(url, false);
 
         callback.on_window_object_ready(&view, frame_id, is_main_frame, &url_str);
-
-        // Prevent drop of view to avoid deallocation
-        std::mem::forget(view);
     }
 }
 
@@ -418,14 +462,11 @@ extern "C" fn dom_ready_callback<T: DOMReadyCallback>(
     url: ULString,
 ) {
     unsafe {
-        let callback = &*(user_data as *const T);
-        let view = View::from_raw(caller);
+        let callback = CallbackData::<T>::get(user_data);
+        let view = CallbackData::<T>::view(user_data, caller);
         let url_str = String::from_raw(url, false);
 
         callback.on_dom_ready(&view, frame_id, is_main_frame, &url_str);
-
-        // Prevent drop of view to avoid deallocation
-        std::mem::forget(view);
     }
 }
 
@@ -434,29 +475,86 @@ extern "C" fn update_history_callback<T: UpdateHistoryCallback>(
     caller: ULView,
 ) {
     unsafe {
-        let callback = &*(user_data as *const T);
-        let view = View::from_raw(caller);
+        let callback = CallbackData::<T>::get(user_data);
+        let view = CallbackData::<T>::view(user_data, caller);
 
         callback.on_update_history(&view);
+    }
+}
+
+/// Forwards title changes to a channel.
+struct TitleSender(mpsc::Sender<std::string::String>);
+
+impl ChangeTitleCallback for TitleSender {
+    fn on_change_title(&self, _view: &View, title: &str) {
+        // The receiver may have been dropped; there is nobody left to notify
+        let _ = self.0.send(title.to_owned());
+    }
+}
+
+/// Forwards URL changes to a channel.
+struct UrlSender(mpsc::Sender<std::string::String>);
+
+impl ChangeURLCallback for UrlSender {
+    fn on_change_url(&self, _view: &View, url: &str) {
+        let _ = self.0.send(url.to_owned());
+    }
+}
+
+/// Runs a handler with the main frame's global object whenever its window object is reset.
+struct WindowObjectReadyHandler<F>(F);
+
+impl<F> WindowObjectReadyCallback for WindowObjectReadyHandler<F>
+where
+    F: for<'c> Fn(&Context<'c>, &Object<'c>) + Send + 'static,
+{
+    fn on_window_object_ready(&self, view: &View, _frame_id: u64, is_main_frame: bool, _url: &str) {
+        if !is_main_frame {
+            return;
+        }
 
-        // Prevent drop of view to avoid deallocation
-        std::mem::forget(view);
+        let locked = view.lock_js_context();
+        let context = locked.context();
+        let global = context.global_object();
+        (self.0)(&context, &global);
     }
 }
 
 /// A structure that holds callback data and keeps it alive.
 struct CallbackData<T: ?Sized> {
+    /// The state of the view the callback was set through.
+    view: Weak<ViewState>,
     data: Box<T>,
 }
 
 impl<T> CallbackData<T> {
-    fn new(data: T) -> *mut c_void {
+    fn new(view: &View, data: T) -> *mut c_void {
         let data = Box::new(CallbackData {
+            view: Rc::downgrade(&view.state),
             data: Box::new(data),
         });
         Box::into_raw(data) as *mut c_void
     }
 
+    /// Borrow the callback behind a pointer returned by `new`.
+    unsafe fn get<'a>(ptr: *mut c_void) -> &'a T {
+        unsafe { &(*(ptr as *const CallbackData<T>)).data }
+    }
+
+    /// Borrow the view a callback is called for.
+    ///
+    /// The view shares the state of the view the callback was set through and
+    /// doesn't destroy the view when dropped.
+    unsafe fn view(ptr: *mut c_void, raw: ULView) -> View {
+        let view = unsafe { &(*(ptr as *const CallbackData<T>)).view };
+        View {
+            raw,
+            owned: false,
+            state: view.upgrade().unwrap_or_default(),
+            _marker: PhantomData,
+        }
+    }
+
     unsafe fn drop(ptr: *mut c_void) {
         unsafe {
             if !ptr.is_null() {
@@ -466,6 +564,36 @@ impl<T> CallbackData<T> {
     }
 }
 
+/// The callbacks a view can register, each holding at most one callback at a time.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum CallbackSlot {
+    ChangeTitle,
+    ChangeUrl,
+    ChangeTooltip,
+    ChangeCursor,
+    AddConsoleMessage,
+    CreateChildView,
+    CreateInspectorView,
+    BeginLoading,
+    FinishLoading,
+    FailLoading,
+    WindowObjectReady,
+    DomReady,
+    UpdateHistory,
+}
+
+/// Callback data registered with Ultralight, freed when dropped.
+struct RegisteredCallback {
+    user_data: *mut c_void,
+    drop: unsafe fn(*mut c_void),
+}
+
+impl Drop for RegisteredCallback {
+    fn drop(&mut self) {
+        unsafe { (self.drop)(self.user_data) }
+    }
+}
+
 /// A structure that manages a locked JavaScript context.
 pub struct LockedJSContext<'a> {
     view: &'a View,
@@ -477,6 +605,26 @@ impl LockedJSContext<'_> {
     pub fn raw(&self) -> JSContextRef {
         self.context
     }
+
+    /// Returns the locked JavaScript context of the view as a safe Context.
+    ///
+    /// The Context borrows the lock guard, so it (and every Value or Object created
+    /// through it) can't be used after the context is unlocked:
+    ///
+    /// ```ignore
+    /// let locked = view.lock_js_context();
+    /// let context = locked.context();
+    /// context.define_function("ping", |context, _function, _this, _arguments| {
+    ///     Ok(Value::string(context, "pong"))
+    /// })?;
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// The view's JavaScript context.
+    pub fn context(&self) -> Context<'_> {
+        unsafe { Context::from_raw(self.raw() as crate::javascript_core::ffi::JSContextRef) }
+    }
 }
 
 impl Drop for LockedJSContext<'_> {
@@ -487,9 +635,197 @@ impl Drop for LockedJSContext<'_> {
     }
 }
 
+/// Build a `file:///` URL for a path, percent-encoding characters that can't
+/// appear in a URL path.
+fn file_url(path: &Path) -> std::string::String {
+    let mut url = std::string::String::from("file:///");
+    let components = path.components().filter_map(|component| match component {
+        Component::Normal(part) => Some(part.to_string_lossy()),
+        // Keep the drive letter of Windows paths, as in `file:///C:/dir/page.html`
+        Component::Prefix(prefix) => match prefix.kind() {
+            Prefix::Disk(drive) | Prefix::VerbatimDisk(drive) => {
+                Some(Cow::Owned(format!("{}:", drive as char)))
+            }
+            _ => None,
+        },
+        _ => None,
+    });
+
+    for (index, part) in components.enumerate() {
+        if index > 0 {
+            url.push('/');
+        }
+        for byte in part.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b':' => {
+                    url.push(byte as char)
+                }
+                _ => url.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+    }
+
+    url
+}
+
+/// Scale a position in logical (CSS) pixels to the nearest device pixel.
+fn logical_to_device(logical_x: f64, logical_y: f64, scale: f64) -> (i32, i32) {
+    (
+        (logical_x * scale).round() as i32,
+        (logical_y * scale).round() as i32,
+    )
+}
+
+/// The loading state of a view's main frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadState {
+    /// No page load has started yet.
+    #[default]
+    NotStarted,
+    /// The main frame is loading.
+    Loading,
+    /// The main frame finished loading.
+    Loaded,
+    /// The main frame failed to load.
+    Failed,
+}
+
+/// The state a view tracks through its loading callbacks.
+#[derive(Default)]
+struct Tracked {
+    load_state: Option<LoadState>,
+    history: History,
+}
+
+/// The main frame URLs a view has loaded, recorded from the begin loading callback.
+#[derive(Default)]
+struct History {
+    entries: Vec<std::string::String>,
+    current: usize,
+    /// The offset of a history navigation started with `go_back` and friends.
+    pending_offset: Option<i32>,
+}
+
+impl History {
+    fn record(&mut self, url: &str) {
+        if let Some(offset) = self.pending_offset.take()
+            && let Some(target) = self.offset_index(offset)
+        {
+            self.current = target;
+            self.entries[target] = url.to_owned();
+            return;
+        }
+
+        // Reloading the current page doesn't add an entry
+        if self
+            .entries
+            .get(self.current)
+            .is_some_and(|entry| entry == url)
+        {
+            return;
+        }
+
+        self.entries.truncate(self.current + 1);
+        self.entries.push(url.to_owned());
+        self.current = self.entries.len() - 1;
+    }
+
+    /// Get the index of the entry at `offset` from the current one, if it exists.
+    fn offset_index(&self, offset: i32) -> Option<usize> {
+        let target = self.current.checked_add_signed(offset as isize)?;
+        (target < self.entries.len()).then_some(target)
+    }
+}
+
+/// Wraps a loading callback so that the view's load state is updated before it runs.
+struct TrackLoadState<T>(T);
+
+/// A loading callback that does nothing, used to track the load state by default.
+struct NoLoadingCallback;
+
+impl BeginLoadingCallback for NoLoadingCallback {
+    fn on_begin_loading(&self, _view: &View, _frame_id: u64, _is_main_frame: bool, _url: &str) {}
+}
+
+impl FinishLoadingCallback for NoLoadingCallback {
+    fn on_finish_loading(&self, _view: &View, _frame_id: u64, _is_main_frame: bool, _url: &str) {}
+}
+
+impl FailLoadingCallback for NoLoadingCallback {
+    fn on_fail_loading(
+        &self,
+        _view: &View,
+        _frame_id: u64,
+        _is_main_frame: bool,
+        _url: &str,
+        _description: &str,
+        _error_domain: &str,
+        _error_code: i32,
+    ) {
+    }
+}
+
+impl<T: BeginLoadingCallback> BeginLoadingCallback for TrackLoadState<T> {
+    fn on_begin_loading(&self, view: &View, frame_id: u64, is_main_frame: bool, url: &str) {
+        view.set_load_state(is_main_frame, LoadState::Loading);
+        view.record_history(is_main_frame, url);
+        self.0.on_begin_loading(view, frame_id, is_main_frame, url);
+    }
+}
+
+impl<T: FinishLoadingCallback> FinishLoadingCallback for TrackLoadState<T> {
+    fn on_finish_loading(&self, view: &View, frame_id: u64, is_main_frame: bool, url: &str) {
+        view.set_load_state(is_main_frame, LoadState::Loaded);
+        self.0.on_finish_loading(view, frame_id, is_main_frame, url);
+    }
+}
+
+impl<T: FailLoadingCallback> FailLoadingCallback for TrackLoadState<T> {
+    fn on_fail_loading(
+        &self,
+        view: &View,
+        frame_id: u64,
+        is_main_frame: bool,
+        url: &str,
+        description: &str,
+        error_domain: &str,
+        error_code: i32,
+    ) {
+        view.set_load_state(is_main_frame, LoadState::Failed);
+        self.0.on_fail_loading(
+            view,
+            frame_id,
+            is_main_frame,
+            url,
+            description,
+            error_domain,
+            error_code,
+        );
+    }
+}
+
 /// A safe wrapper around Ultralight's ULView type.
+///
+/// Views are bound to the thread that created their renderer and are neither
+/// `Send` nor `Sync`. All calls, including callbacks, happen on that thread.
 pub struct View {
     raw: ULView,
+    /// Whether dropping this view destroys it. The views passed to callbacks are
+    /// borrowed and don't.
+    owned: bool,
+    /// The state shared with the views passed to callbacks set through this view.
+    state: Rc<ViewState>,
+    _marker: PhantomData<*const ()>,
+}
+
+/// The state a view shares with the views passed to its callbacks.
+#[derive(Default)]
+struct ViewState {
+    /// The callback data registered through the view. It is freed after the view
+    /// is destroyed, or when the callback is replaced.
+    callbacks: RefCell<HashMap<CallbackSlot, RegisteredCallback>>,
+    /// The state updated by the loading callbacks set through the view.
+    tracked: RefCell<Tracked>,
 }
 
 impl View {
@@ -508,7 +844,16 @@ impl View {
             };
 
             let raw = ulCreateView(renderer.raw(), width, height, config.raw(), session_ptr);
-            Self { raw }
+            let view = Self {
+                raw,
+                owned: true,
+                state: Rc::default(),
+                _marker: PhantomData,
+            };
+            view.set_begin_loading_callback(NoLoadingCallback);
+            view.set_finish_loading_callback(NoLoadingCallback);
+            view.set_fail_loading_callback(NoLoadingCallback);
+            view
         }
     }
 
@@ -518,7 +863,12 @@ impl View {
     ///
     /// The pointer must be a valid ULView created by the Ultralight API.
     pub unsafe fn from_raw(raw: ULView) -> Self {
-        Self { raw }
+        Self {
+            raw,
+            owned: true,
+            state: Rc::default(),
+            _marker: PhantomData,
+        }
     }
 
     /// Get a reference to the raw ULView.
@@ -591,6 +941,23 @@ impl View {
         unsafe { ulViewIsLoading(self.raw) }
     }
 
+    /// Get the loading state of the main frame.
+    ///
+    /// The state is tracked through the begin, finish and fail loading callbacks,
+    /// which keeps working when callbacks are set with `set_begin_loading_callback`
+    /// and friends. Views wrapped with `from_raw` only track loads from callbacks
+    /// set through them, and until then report `Loading` or `NotStarted`, based
+    /// on `is_loading`.
+    pub fn load_state(&self) -> LoadState {
+        self.state.tracked.borrow().load_state.unwrap_or_else(|| {
+            if self.is_loading() {
+                LoadState::Loading
+            } else {
+                LoadState::NotStarted
+            }
+        })
+    }
+
     /// Get the render target (for GPU-accelerated views).
     pub fn render_target(&self) -> RenderTarget {
         unsafe {
@@ -599,6 +966,45 @@ impl View {
         }
     }
 
+    /// Copy the rendered pixels of the view as BGRA8.
+    ///
+    /// This only works for CPU-rendered views, which paint into a surface. The pixels
+    /// of accelerated views live in a GPU texture that can only be read back through
+    /// the GPU driver, so `None` is returned for them; use `render_target` and
+    /// `RenderTarget::texture_rect` to locate the view within that texture instead.
+    pub fn copy_render_target_bitmap(&self) -> Option<Image> {
+        if self.is_accelerated() {
+            return None;
+        }
+
+        self.surface()?.read_pixels()
+    }
+
+    /// Copy the rendered pixels of the view as tightly packed BGRA8.
+    ///
+    /// Returns the width, height and pixel bytes, with the row padding of the surface
+    /// removed. Like `copy_render_target_bitmap`, this only works for CPU-rendered
+    /// views.
+    pub fn read_pixels(&self) -> Result<(u32, u32, Vec<u8>), Error> {
+        if self.is_accelerated() {
+            return Err(Error::InvalidOperation(
+                "accelerated views have no CPU surface to read",
+            ));
+        }
+
+        let surface = self
+            .surface()
+            .ok_or(Error::NullReference("view has no surface"))?;
+        if surface.width() == 0 || surface.height() == 0 {
+            return Ok((surface.width(), surface.height(), Vec::new()));
+        }
+
+        let image = surface
+            .read_pixels()
+            .ok_or(Error::UltralightError("failed to lock surface pixels"))?;
+        Ok((image.width(), image.height(), image.pixels))
+    }
+
     /// Get the surface (for CPU-rendered views).
     pub fn surface(&self) -> Option<Surface> {
         unsafe {
@@ -627,6 +1033,34 @@ impl View {
         }
     }
 
+    /// Load a local HTML file.
+    ///
+    /// The file is loaded through a `file:///` URL, so relative references to
+    /// stylesheets, scripts and images resolve against the file's directory. Ultralight
+    /// serves `file:///` URLs through the platform file system, which must be able
+    /// to read the file and its assets:
+    ///
+    /// - With `app_core::platform::enable_platform_file_system`, paths are relative
+    ///   to the base directory, so the file must be inside it.
+    /// - With a custom file system, the URL path is the file's absolute path.
+    pub fn load_file(&self, path: &Path) -> Result<(), Error> {
+        let path = path.canonicalize()?;
+        let url_path = match platform::file_system_base_dir() {
+            Some(base_dir) => path
+                .strip_prefix(base_dir.canonicalize()?)
+                .map_err(|_| {
+                    Error::InvalidArgument(
+                        "file is outside the platform file system base directory",
+                    )
+                })?
+                .to_path_buf(),
+            None => path,
+        };
+
+        self.load_url(&file_url(&url_path));
+        Ok(())
+    }
+
     /// Resize the view.
     pub fn resize(&self, width: u32, height: u32) {
         unsafe {
@@ -646,6 +1080,10 @@ impl View {
     }
 
     /// Evaluate JavaScript.
+    ///
+    /// If the script throws, this returns `Error::JavaScriptError` with the
+    /// exception converted to a string. Use `evaluate_script_with` to get the
+    /// location and stack of the exception.
     pub fn evaluate_script(&self, js: &str) -> Result<String, Error> {
         let js_str = String::from_str(js);
         let mut exception: ULString = ptr::null_mut();
@@ -662,6 +1100,59 @@ impl View {
         }
     }
 
+    /// Evaluate JavaScript with a source URL and starting line number.
+    ///
+    /// Stack traces and error messages refer to `source_url` instead of an
+    /// anonymous script. If the script throws, this returns
+    /// `Error::JavaScriptException` with the location and stack of the exception.
+    pub fn evaluate_script_with(
+        &self,
+        js: &str,
+        source_url: Option<&str>,
+        starting_line: i32,
+    ) -> Result<String, Error> {
+        let locked = self.lock_js_context();
+        let context = locked.context();
+        let result = context
+            .evaluate_script(js, None, source_url, starting_line)
+            .and_then(|value| value.to_string())?;
+        Ok(String::from(&result))
+    }
+
+    /// Scroll the document to an absolute position, in CSS pixels.
+    ///
+    /// This calls `window.scrollTo`, so it only moves the main document viewport;
+    /// overflow containers inside the page keep their own scroll position.
+    pub fn scroll_to(&self, x: i32, y: i32) -> Result<(), Error> {
+        self.evaluate_script(&format!("window.scrollTo({}, {})", x, y))?;
+        Ok(())
+    }
+
+    /// Scroll the document by a relative offset, in CSS pixels.
+    ///
+    /// This calls `window.scrollBy` and has the same limitations as `scroll_to`.
+    pub fn scroll_by(&self, dx: i32, dy: i32) -> Result<(), Error> {
+        self.evaluate_script(&format!("window.scrollBy({}, {})", dx, dy))?;
+        Ok(())
+    }
+
+    /// Get the scroll position of the document, in CSS pixels.
+    ///
+    /// This reads `window.scrollX` and `window.scrollY`, which are clamped to the
+    /// scrollable area and don't reflect the scroll position of overflow containers.
+    pub fn scroll_position(&self) -> Result<(i32, i32), Error> {
+        let position = self
+            .evaluate_script("Math.round(window.scrollX) + ',' + Math.round(window.scrollY)")?
+            .to_string();
+        let invalid = || Error::InvalidOperation("scroll position is not a pair of numbers");
+
+        let (x, y) = position.split_once(',').ok_or_else(invalid)?;
+        Ok((
+            x.parse().map_err(|_| invalid())?,
+            y.parse().map_err(|_| invalid())?,
+        ))
+    }
+
     /// Check if can navigate backwards in history.
     pub fn can_go_back(&self) -> bool {
         unsafe { ulViewCanGoBack(self.raw) }
@@ -672,8 +1163,69 @@ impl View {
         unsafe { ulViewCanGoForward(self.raw) }
     }
 
+    /// Get the number of entries in the session history, as reported by `history.length`.
+    ///
+    /// Ultralight and the DOM don't expose the current position in the history, only
+    /// whether navigating back or forward is possible.
+    pub fn history_length(&self) -> Result<u32, Error> {
+        self.evaluate_script("history.length")?
+            .to_string()
+            .parse()
+            .map_err(|_| Error::InvalidOperation("history.length did not return a number"))
+    }
+
+    /// Get the number of main frame URLs recorded in this view's tracked history.
+    ///
+    /// Ultralight doesn't expose its history list, so it is tracked in Rust from the
+    /// begin loading callback. Navigations within a document, such as
+    /// `history.pushState` or changing the fragment, don't start a load and are not
+    /// recorded, so this can differ from `history_length`, which asks JavaScript.
+    /// History navigations started from JavaScript, such as `history.back()`, are
+    /// recorded as new entries because only `go_back` and friends are seen. Views
+    /// wrapped with `from_raw` are only tracked once a begin loading callback is
+    /// set through them.
+    pub fn tracked_history_len(&self) -> usize {
+        self.with_history(|history| history.entries.len())
+    }
+
+    /// Get the URL of an entry in the tracked history, oldest first.
+    ///
+    /// See `tracked_history_len` for how the history is tracked.
+    pub fn tracked_history_entry(&self, index: usize) -> Option<String> {
+        self.with_history(|history| history.entries.get(index).map(|url| String::from_str(url)))
+    }
+
+    /// Get the index of the current entry in the tracked history.
+    ///
+    /// Returns `None` if nothing has been loaded yet.
+    pub fn tracked_history_index(&self) -> Option<usize> {
+        self.with_history(|history| (!history.entries.is_empty()).then_some(history.current))
+    }
+
+    /// Run `f` on the tracked history.
+    fn with_history<R>(&self, f: impl FnOnce(&mut History) -> R) -> R {
+        f(&mut self.state.tracked.borrow_mut().history)
+    }
+
+    /// Record the load state of the main frame.
+    fn set_load_state(&self, is_main_frame: bool, state: LoadState) {
+        if is_main_frame {
+            self.state.tracked.borrow_mut().load_state = Some(state);
+        }
+    }
+
+    /// Record a main frame URL in the tracked history.
+    fn record_history(&self, is_main_frame: bool, url: &str) {
+        if is_main_frame {
+            self.with_history(|history| history.record(url));
+        }
+    }
+
     /// Navigate backwards in history.
     pub fn go_back(&self) {
+        if self.can_go_back() {
+            self.with_history(|history| history.pending_offset = Some(-1));
+        }
         unsafe {
             ulViewGoBack(self.raw);
         }
@@ -681,6 +1233,9 @@ impl View {
 
     /// Navigate forwards in history.
     pub fn go_forward(&self) {
+        if self.can_go_forward() {
+            self.with_history(|history| history.pending_offset = Some(1));
+        }
         unsafe {
             ulViewGoForward(self.raw);
         }
@@ -688,6 +1243,11 @@ impl View {
 
     /// Navigate to an arbitrary offset in history.
     pub fn go_to_history_offset(&self, offset: i32) {
+        self.with_history(|history| {
+            if offset != 0 && history.offset_index(offset).is_some() {
+                history.pending_offset = Some(offset);
+            }
+        });
         unsafe {
             ulViewGoToHistoryOffset(self.raw, offset);
         }
@@ -739,12 +1299,30 @@ impl View {
     }
 
     /// Fire a mouse event.
+    ///
+    /// The event coordinates are in device pixels relative to the view, i.e. CSS
+    /// pixels multiplied by `device_scale`. Use `fire_mouse_event_logical` to pass
+    /// CSS pixels instead.
     pub fn fire_mouse_event(&self, event: &MouseEvent) {
         unsafe {
             ulViewFireMouseEvent(self.raw, event.raw());
         }
     }
 
+    /// Fire a mouse event at a position in logical (CSS) pixels.
+    ///
+    /// The position is scaled by `device_scale` and rounded to device pixels.
+    pub fn fire_mouse_event_logical(
+        &self,
+        event_type: MouseEventType,
+        logical_x: f64,
+        logical_y: f64,
+        button: MouseButton,
+    ) {
+        let (x, y) = logical_to_device(logical_x, logical_y, self.device_scale());
+        self.fire_mouse_event(&MouseEvent::new(event_type, x, y, button));
+    }
+
     /// Fire a scroll event.
     pub fn fire_scroll_event(&self, event: &ScrollEvent) {
         unsafe {
@@ -752,10 +1330,16 @@ impl View {
         }
     }
 
+    /// Click the left mouse button at the specified coordinates, in device pixels.
+    pub fn click(&self, x: i32, y: i32) {
+        self.fire_mouse_event(&MouseEvent::down(x, y, MouseButton::kMouseButton_Left));
+        self.fire_mouse_event(&MouseEvent::up(x, y, MouseButton::kMouseButton_Left));
+    }
+
     /// Set callback for when the page title changes.
     pub fn set_change_title_callback<T: 'static + ChangeTitleCallback>(&self, callback: T) {
         unsafe {
-            let user_data = CallbackData::new(callback);
+            let user_data = CallbackData::new(self, callback);
             ulViewSetChangeTitleCallback(
                 self.raw,
                 std::mem::transmute(
@@ -763,13 +1347,14 @@ impl View {
                 ),
                 user_data,
             );
+            self.retain_callback::<T>(CallbackSlot::ChangeTitle, user_data);
         }
     }
 
     /// Set callback for when the page URL changes.
     pub fn set_change_url_callback<T: 'static + ChangeURLCallback>(&self, callback: T) {
         unsafe {
-            let user_data = CallbackData::new(callback);
+            let user_data = CallbackData::new(self, callback);
             ulViewSetChangeURLCallback(
                 self.raw,
                 std::mem::transmute(
@@ -777,13 +1362,38 @@ impl View {
                 ),
                 user_data,
             );
+            self.retain_callback::<T>(CallbackSlot::ChangeUrl, user_data);
         }
     }
 
+    /// Get a channel that receives the page title whenever it changes.
+    ///
+    /// This installs a change-title callback, replacing any existing one. The
+    /// sender is dropped when that callback is replaced or the view drops, which
+    /// disconnects the receiver. Events are sent on the renderer thread while
+    /// `Renderer::update` runs.
+    pub fn title_changes(&self) -> mpsc::Receiver<std::string::String> {
+        let (sender, receiver) = mpsc::channel();
+        self.set_change_title_callback(TitleSender(sender));
+        receiver
+    }
+
+    /// Get a channel that receives the page URL whenever it changes.
+    ///
+    /// This installs a change-URL callback, replacing any existing one. The
+    /// sender is dropped when that callback is replaced or the view drops, which
+    /// disconnects the receiver. Events are sent on the renderer thread while
+    /// `Renderer::update` runs.
+    pub fn url_changes(&self) -> mpsc::Receiver<std::string::String> {
+        let (sender, receiver) = mpsc::channel();
+        self.set_change_url_callback(UrlSender(sender));
+        receiver
+    }
+
     /// Set callback for when the tooltip changes.
     pub fn set_change_tooltip_callback<T: 'static + ChangeTooltipCallback>(&self, callback: T) {
         unsafe {
-            let user_data = CallbackData::new(callback);
+            let user_data = CallbackData::new(self, callback);
             ulViewSetChangeTooltipCallback(
                 self.raw,
                 std::mem::transmute(
@@ -791,13 +1401,14 @@ impl View {
                 ),
                 user_data,
             );
+            self.retain_callback::<T>(CallbackSlot::ChangeTooltip, user_data);
         }
     }
 
     /// Set callback for when the cursor changes.
     pub fn set_change_cursor_callback<T: 'static + ChangeCursorCallback>(&self, callback: T) {
         unsafe {
-            let user_data = CallbackData::new(callback);
+            let user_data = CallbackData::new(self, callback);
             ulViewSetChangeCursorCallback(
                 self.raw,
                 std::mem::transmute(
@@ -805,6 +1416,7 @@ impl View {
                 ),
                 user_data,
             );
+            self.retain_callback::<T>(CallbackSlot::ChangeCursor, user_data);
         }
     }
 
@@ -814,7 +1426,7 @@ impl View {
         callback: T,
     ) {
         unsafe {
-            let user_data = CallbackData::new(callback);
+            let user_data = CallbackData::new(self, callback);
             ulViewSetAddConsoleMessageCallback(
                 self.raw,
                 std::mem::transmute(
@@ -823,6 +1435,7 @@ impl View {
                 ),
                 user_data,
             );
+            self.retain_callback::<T>(CallbackSlot::AddConsoleMessage, user_data);
         }
     }
 
@@ -832,7 +1445,7 @@ impl View {
         callback: T,
     ) {
         unsafe {
-            let user_data = CallbackData::new(callback);
+            let user_data = CallbackData::new(self, callback);
             ulViewSetCreateChildViewCallback(
                 self.raw,
                 std::mem::transmute(
@@ -841,6 +1454,7 @@ impl View {
                 ),
                 user_data,
             );
+            self.retain_callback::<T>(CallbackSlot::CreateChildView, user_data);
         }
     }
 
@@ -850,7 +1464,7 @@ impl View {
         callback: T,
     ) {
         unsafe {
-            let user_data = CallbackData::new(callback);
+            let user_data = CallbackData::new(self, callback);
             ulViewSetCreateInspectorViewCallback(
                 self.raw,
                 std::mem::transmute(
@@ -859,47 +1473,50 @@ impl View {
                 ),
                 user_data,
             );
+            self.retain_callback::<T>(CallbackSlot::CreateInspectorView, user_data);
         }
     }
 
     /// Set callback for when the page begins loading a new URL into a frame.
     pub fn set_begin_loading_callback<T: 'static + BeginLoadingCallback>(&self, callback: T) {
         unsafe {
-            let user_data = CallbackData::new(callback);
+            let user_data = CallbackData::new(self, TrackLoadState(callback));
             ulViewSetBeginLoadingCallback(
                 self.raw,
                 std::mem::transmute(
-                    begin_loading_callback::<T>
+                    begin_loading_callback::<TrackLoadState<T>>
                         as extern "C" fn(*mut c_void, *mut _, u64, bool, *mut _),
                 ),
                 user_data,
             );
+            self.retain_callback::<TrackLoadState<T>>(CallbackSlot::BeginLoading, user_data);
         }
     }
 
     /// Set callback for when the page finishes loading a URL into a frame.
     pub fn set_finish_loading_callback<T: 'static + FinishLoadingCallback>(&self, callback: T) {
         unsafe {
-            let user_data = CallbackData::new(callback);
+            let user_data = CallbackData::new(self, TrackLoadState(callback));
             ulViewSetFinishLoadingCallback(
                 self.raw,
                 std::mem::transmute(
-                    finish_loading_callback::<T>
+                    finish_loading_callback::<TrackLoadState<T>>
                         as extern "C" fn(*mut c_void, *mut _, u64, bool, *mut _),
                 ),
                 user_data,
             );
+            self.retain_callback::<TrackLoadState<T>>(CallbackSlot::FinishLoading, user_data);
         }
     }
 
     /// Set callback for when an error occurs while loading a URL into a frame.
     pub fn set_fail_loading_callback<T: 'static + FailLoadingCallback>(&self, callback: T) {
         unsafe {
-            let user_data = CallbackData::new(callback);
+            let user_data = CallbackData::new(self, TrackLoadState(callback));
             ulViewSetFailLoadingCallback(
                 self.raw,
                 std::mem::transmute(
-                    fail_loading_callback::<T>
+                    fail_loading_callback::<TrackLoadState<T>>
                         as extern "C" fn(
                             *mut c_void,
                             *mut _,
@@ -913,6 +1530,7 @@ impl View {
                 ),
                 user_data,
             );
+            self.retain_callback::<TrackLoadState<T>>(CallbackSlot::FailLoading, user_data);
         }
     }
 
@@ -922,7 +1540,7 @@ impl View {
         callback: T,
     ) {
         unsafe {
-            let user_data = CallbackData::new(callback);
+            let user_data = CallbackData::new(self, callback);
             ulViewSetWindowObjectReadyCallback(
                 self.raw,
                 std::mem::transmute(
@@ -931,13 +1549,39 @@ impl View {
                 ),
                 user_data,
             );
+            self.retain_callback::<T>(CallbackSlot::WindowObjectReady, user_data);
         }
     }
 
+    /// Sets a handler that runs each time the main frame's window object is reset.
+    ///
+    /// This is the place to expose Rust functions and objects to a page, since the
+    /// global object is replaced on every navigation. The handler receives the
+    /// view's JavaScript context, locked for the duration of the call, and its
+    /// global object; neither can be kept beyond the call. This replaces any
+    /// callback set with `set_window_object_ready_callback`.
+    ///
+    /// ```ignore
+    /// view.set_window_object_ready_handler(|context, global| {
+    ///     let ping = Value::function(context, |context, _arguments| Ok(Value::string(context, "pong")));
+    ///     let _ = global.set_property("hostPing", ping, PropertyAttributes::NONE);
+    /// });
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The handler to run with the context and global object.
+    pub fn set_window_object_ready_handler<F>(&self, handler: F)
+    where
+        F: for<'c> Fn(&Context<'c>, &Object<'c>) + Send + 'static,
+    {
+        self.set_window_object_ready_callback(WindowObjectReadyHandler(handler));
+    }
+
     /// Set callback for when all JavaScript has been parsed and the document is ready.
     pub fn set_dom_ready_callback<T: 'static + DOMReadyCallback>(&self, callback: T) {
         unsafe {
-            let user_data = CallbackData::new(callback);
+            let user_data = CallbackData::new(self, callback);
             ulViewSetDOMReadyCallback(
                 self.raw,
                 std::mem::transmute(
@@ -946,13 +1590,14 @@ impl View {
                 ),
                 user_data,
             );
+            self.retain_callback::<T>(CallbackSlot::DomReady, user_data);
         }
     }
 
     /// Set callback for when the history is modified.
     pub fn set_update_history_callback<T: 'static + UpdateHistoryCallback>(&self, callback: T) {
         unsafe {
-            let user_data = CallbackData::new(callback);
+            let user_data = CallbackData::new(self, callback);
             ulViewSetUpdateHistoryCallback(
                 self.raw,
                 std::mem::transmute(
@@ -960,9 +1605,21 @@ impl View {
                 ),
                 user_data,
             );
+            self.retain_callback::<T>(CallbackSlot::UpdateHistory, user_data);
         }
     }
 
+    /// Keep the callback data registered for `slot` alive until the callback is
+    /// replaced or the view drops, freeing the data it replaces.
+    fn retain_callback<T>(&self, slot: CallbackSlot, user_data: *mut c_void) {
+        let registered = RegisteredCallback {
+            user_data,
+            drop: CallbackData::<T>::drop,
+        };
+        let previous = self.state.callbacks.borrow_mut().insert(slot, registered);
+        drop(previous);
+    }
+
     /// Set whether the view should be repainted during the next render call.
     pub fn set_needs_paint(&self, needs_paint: bool) {
         unsafe {
@@ -985,10 +1642,119 @@ impl View {
 
 impl Drop for View {
     fn drop(&mut self) {
-        if !self.raw.is_null() {
+        if self.owned && !self.raw.is_null() {
             unsafe {
                 ulDestroyView(self.raw);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(urls: &[&str]) -> History {
+        let mut history = History::default();
+        for url in urls {
+            history.record(url);
+        }
+        history
+    }
+
+    #[test]
+    fn history_records_new_urls() {
+        let history = history(&["https://a/", "https://b/"]);
+        assert_eq!(history.entries, ["https://a/", "https://b/"]);
+        assert_eq!(history.current, 1);
+    }
+
+    #[test]
+    fn history_ignores_reload() {
+        let history = history(&["https://a/", "https://b/", "https://b/"]);
+        assert_eq!(history.entries, ["https://a/", "https://b/"]);
+        assert_eq!(history.current, 1);
+    }
+
+    #[test]
+    fn history_navigation_moves_current_entry() {
+        let mut history = history(&["https://a/", "https://b/", "https://c/"]);
+        history.pending_offset = Some(-2);
+        history.record("https://a/");
+        assert_eq!(history.current, 0);
+        assert_eq!(history.entries.len(), 3);
+        assert!(history.pending_offset.is_none());
+    }
+
+    #[test]
+    fn history_new_navigation_after_back_drops_forward_entries() {
+        let mut history = history(&["https://a/", "https://b/", "https://c/"]);
+        history.pending_offset = Some(-1);
+        history.record("https://b/");
+        history.record("https://d/");
+        assert_eq!(history.entries, ["https://a/", "https://b/", "https://d/"]);
+        assert_eq!(history.current, 2);
+    }
+
+    #[test]
+    fn history_offset_index_stays_in_bounds() {
+        let history = history(&["https://a/", "https://b/"]);
+        assert_eq!(history.offset_index(-1), Some(0));
+        assert_eq!(history.offset_index(0), Some(1));
+        assert_eq!(history.offset_index(1), None);
+        assert_eq!(history.offset_index(-2), None);
+    }
+
+    fn console_message(level: MessageLevel, source_id: &str) -> ConsoleMessage {
+        ConsoleMessage {
+            source: MessageSource::kMessageSource_ConsoleAPI,
+            level,
+            text: "boom".to_owned(),
+            line: 3,
+            column: 9,
+            source_id: source_id.to_owned(),
+        }
+    }
+
+    #[test]
+    fn console_message_display_includes_its_location() {
+        let message = console_message(MessageLevel::kMessageLevel_Error, "file:///index.html");
+        assert_eq!(message.to_string(), "[error] boom (file:///index.html:3:9)");
+    }
+
+    #[test]
+    fn console_message_display_omits_a_missing_location() {
+        let message = console_message(MessageLevel::kMessageLevel_Warning, "");
+        assert_eq!(message.to_string(), "[warning] boom");
+    }
+
+    #[test]
+    fn logical_positions_are_scaled_to_device_pixels() {
+        assert_eq!(logical_to_device(10.0, 10.0, 2.0), (20, 20));
+        assert_eq!(logical_to_device(10.0, 5.0, 1.0), (10, 5));
+        assert_eq!(logical_to_device(10.3, 0.5, 1.5), (15, 1));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_url_percent_encodes_the_path() {
+        assert_eq!(
+            file_url(Path::new("/home/me/My Page#1.html")),
+            "file:///home/me/My%20Page%231.html"
+        );
+        assert_eq!(file_url(Path::new("/tmp/a-b_c.~d")), "file:///tmp/a-b_c.~d");
+        assert_eq!(
+            file_url(Path::new("/tmp/caf\u{e9}.html")),
+            "file:///tmp/caf%C3%A9.html"
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn file_url_keeps_the_drive_letter() {
+        assert_eq!(
+            file_url(Path::new(r"C:\dir\my page.html")),
+            "file:///C:/dir/my%20page.html"
+        );
+    }
+}
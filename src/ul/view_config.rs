@@ -22,6 +22,11 @@ impl ViewConfig {
         }
     }
 
+    /// Create a builder for a view config, starting from Ultralight's defaults.
+    pub fn builder() -> ViewConfigBuilder {
+        ViewConfigBuilder::new()
+    }
+
     /// Get a reference to the raw ULViewConfig.
     pub fn raw(&self) -> ULViewConfig {
         self.raw
@@ -129,6 +134,75 @@ impl ViewConfig {
     }
 }
 
+/// A builder for ViewConfig.
+///
+/// Options that are not set keep Ultralight's defaults.
+pub struct ViewConfigBuilder {
+    config: ViewConfig,
+}
+
+impl ViewConfigBuilder {
+    /// Create a new builder with default values.
+    pub fn new() -> Self {
+        Self {
+            config: ViewConfig::new(),
+        }
+    }
+
+    /// Set whether to use GPU rendering.
+    pub fn accelerated(mut self, is_accelerated: bool) -> Self {
+        self.config.set_is_accelerated(is_accelerated);
+        self
+    }
+
+    /// Set whether the View should be transparent.
+    pub fn transparent(mut self, is_transparent: bool) -> Self {
+        self.config.set_is_transparent(is_transparent);
+        self
+    }
+
+    /// Set the initial device scale.
+    pub fn device_scale(mut self, scale: f64) -> Self {
+        self.config.set_initial_device_scale(scale);
+        self
+    }
+
+    /// Set whether JavaScript should be enabled.
+    pub fn enable_javascript(mut self, enabled: bool) -> Self {
+        self.config.set_enable_javascript(enabled);
+        self
+    }
+
+    /// Set whether images should be enabled.
+    pub fn enable_images(mut self, enabled: bool) -> Self {
+        self.config.set_enable_images(enabled);
+        self
+    }
+
+    /// Set the user agent string.
+    pub fn user_agent(mut self, agent_string: &str) -> Self {
+        self.config.set_user_agent(agent_string);
+        self
+    }
+
+    /// Set the default font family for standard fonts.
+    pub fn font_family_standard(mut self, font_name: &str) -> Self {
+        self.config.set_font_family_standard(font_name);
+        self
+    }
+
+    /// Build the view config.
+    pub fn build(self) -> ViewConfig {
+        self.config
+    }
+}
+
+impl Default for ViewConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Default for ViewConfig {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,120 @@
+//! Tests for the pixel helpers of Bitmap.
+//!
+//! These need the Ultralight libraries at runtime, so they are ignored by default.
+//! Run them with `cargo test -- --ignored` on a machine with the SDK installed.
+
+use ul::{AlphaExpansion, Bitmap, BitmapFormat};
+
+/// Create an A8 bitmap whose rows hold `rows`, leaving any row padding untouched.
+fn a8_bitmap(rows: &[&[u8]]) -> Bitmap {
+    let bitmap = Bitmap::new(
+        rows[0].len() as u32,
+        rows.len() as u32,
+        BitmapFormat::kBitmapFormat_A8_UNORM,
+    );
+    let row_bytes = bitmap.row_bytes() as usize;
+    let mut pixels = bitmap.lock_pixels().unwrap();
+    for (y, row) in rows.iter().enumerate() {
+        pixels.as_slice_mut()[y * row_bytes..][..row.len()].copy_from_slice(row);
+    }
+    drop(pixels);
+    bitmap
+}
+
+/// Read the pixel bytes of each row of `bitmap`, without the row padding.
+fn rows(bitmap: &Bitmap) -> Vec<Vec<u8>> {
+    let row_len = (bitmap.width() * bitmap.bpp()) as usize;
+    let row_bytes = bitmap.row_bytes() as usize;
+    let pixels = bitmap.lock_pixels().unwrap();
+    (0..bitmap.height() as usize)
+        .map(|y| pixels.as_slice()[y * row_bytes..][..row_len].to_vec())
+        .collect()
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn convert_to_round_trips_a8_through_bgra() {
+    // An odd width, so the rows of the A8 bitmap may be padded
+    let a8 = a8_bitmap(&[&[0x00, 0x40, 0xFF], &[0x80, 0x10, 0x20]]);
+
+    let bgra = a8
+        .convert_to(BitmapFormat::kBitmapFormat_BGRA8_UNORM_SRGB)
+        .unwrap();
+    assert_eq!(bgra.format(), BitmapFormat::kBitmapFormat_BGRA8_UNORM_SRGB);
+    assert_eq!((bgra.width(), bgra.height()), (3, 2));
+    assert_eq!(
+        rows(&bgra)[0],
+        [
+            0x00, 0x00, 0x00, 0x00, 0x40, 0x40, 0x40, 0x40, 0xFF, 0xFF, 0xFF, 0xFF
+        ]
+    );
+
+    let back = bgra
+        .convert_to(BitmapFormat::kBitmapFormat_A8_UNORM)
+        .unwrap();
+    assert_eq!(back.format(), BitmapFormat::kBitmapFormat_A8_UNORM);
+    assert_eq!(rows(&back), rows(&a8));
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn convert_to_the_same_format_copies_the_bitmap() {
+    let a8 = a8_bitmap(&[&[1, 2], &[3, 4]]);
+    let copy = a8.convert_to(BitmapFormat::kBitmapFormat_A8_UNORM).unwrap();
+    assert_ne!(copy.raw(), a8.raw());
+    assert_eq!(rows(&copy), rows(&a8));
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn to_bgra_expands_a8_pixels() {
+    let a8 = a8_bitmap(&[&[0x00, 0x80]]);
+
+    let all_channels = a8.to_bgra(AlphaExpansion::AllChannels).unwrap();
+    assert_eq!(
+        all_channels.format(),
+        BitmapFormat::kBitmapFormat_BGRA8_UNORM_SRGB
+    );
+    assert_eq!(
+        rows(&all_channels),
+        [[0x00, 0x00, 0x00, 0x00, 0x80, 0x80, 0x80, 0x80]]
+    );
+
+    let white_color = a8.to_bgra(AlphaExpansion::WhiteColor).unwrap();
+    assert_eq!(
+        rows(&white_color),
+        [[0xFF, 0xFF, 0xFF, 0x00, 0xFF, 0xFF, 0xFF, 0x80]]
+    );
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn set_pixel_is_read_back_by_get_pixel() {
+    let bgra = Bitmap::new(3, 3, BitmapFormat::kBitmapFormat_BGRA8_UNORM_SRGB);
+    bgra.erase();
+    bgra.set_pixel(1, 2, [0x10, 0x20, 0x30, 0x40]).unwrap();
+    assert_eq!(bgra.get_pixel(1, 2), Some([0x10, 0x20, 0x30, 0x40]));
+    assert_eq!(bgra.get_pixel(2, 1), Some([0, 0, 0, 0]));
+    assert_eq!(bgra.get_pixel(3, 0), None);
+    assert!(bgra.set_pixel(0, 3, [0; 4]).is_err());
+
+    let a8 = Bitmap::new(3, 3, BitmapFormat::kBitmapFormat_A8_UNORM);
+    a8.set_pixel(2, 1, [0x10, 0x20, 0x30, 0x40]).unwrap();
+    assert_eq!(a8.get_pixel(2, 1), Some([0x40; 4]));
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn fill_colors_every_pixel() {
+    let bitmap = Bitmap::new(4, 4, BitmapFormat::kBitmapFormat_BGRA8_UNORM_SRGB);
+    bitmap.fill(0xFF, 0x00, 0x00, 0xFF).unwrap();
+    assert_eq!(bitmap.get_pixel(0, 0), Some([0xFF, 0x00, 0x00, 0xFF]));
+    assert_eq!(bitmap.get_pixel(2, 1), Some([0xFF, 0x00, 0x00, 0xFF]));
+    assert_eq!(bitmap.get_pixel(3, 3), Some([0xFF, 0x00, 0x00, 0xFF]));
+    assert!(
+        rows(&bitmap)
+            .iter()
+            .flatten()
+            .eq([0x00, 0x00, 0xFF, 0xFF].iter().cycle().take(64))
+    );
+}
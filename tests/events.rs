@@ -0,0 +1,88 @@
+//! Tests that synthesized input events are freed.
+//!
+//! These need the Ultralight libraries at runtime, so they are ignored by default.
+//! Run them with `cargo test -- --ignored` on a machine with the SDK installed.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicIsize, Ordering};
+use ul::ffi::ulDestroyMouseEvent;
+use ul::{KeyEvent, KeyEventType, MouseButton, MouseEvent};
+
+/// Counts the bytes allocated through the Rust allocator that are still live.
+///
+/// Ultralight allocates the events themselves with its own allocator, so this only
+/// sees what the wrappers allocate, such as the text of key events.
+struct CountingAllocator;
+
+static LIVE_BYTES: AtomicIsize = AtomicIsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE_BYTES.fetch_add(layout.size() as isize, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size() as isize, Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Get the resident set size of the process in pages.
+#[cfg(target_os = "linux")]
+fn resident_pages() -> usize {
+    let statm = std::fs::read_to_string("/proc/self/statm").unwrap();
+    statm.split_whitespace().nth(1).unwrap().parse().unwrap()
+}
+
+fn key_event(text: &str) -> KeyEvent {
+    KeyEvent::new(
+        KeyEventType::kKeyEventType_Char,
+        0,
+        0,
+        0,
+        text,
+        text,
+        false,
+        false,
+        false,
+    )
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn dropped_events_do_not_accumulate() {
+    // Warm up anything Ultralight initializes lazily
+    drop(MouseEvent::moved(0, 0));
+    drop(key_event("a"));
+
+    let before = LIVE_BYTES.load(Ordering::Relaxed);
+    #[cfg(target_os = "linux")]
+    let pages_before = resident_pages();
+
+    for i in 0..10_000 {
+        drop(MouseEvent::down(i, i, MouseButton::kMouseButton_Left));
+        drop(key_event("a"));
+    }
+
+    let growth = LIVE_BYTES.load(Ordering::Relaxed) - before;
+    assert!(
+        growth < 4096,
+        "{growth} bytes still live after dropping 10k events"
+    );
+
+    // Leaking 20k events would take well over 64 pages
+    #[cfg(target_os = "linux")]
+    assert!(resident_pages().saturating_sub(pages_before) < 64);
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn into_raw_hands_the_event_to_the_caller() {
+    let raw = MouseEvent::moved(1, 2).into_raw();
+    assert!(!raw.is_null());
+    unsafe { ulDestroyMouseEvent(raw) };
+}
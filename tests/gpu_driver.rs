@@ -0,0 +1,118 @@
+//! Tests that render through a custom GPU driver.
+//!
+//! These need the Ultralight libraries and resources at runtime, so they are
+//! ignored by default. Run them with `cargo test -- --ignored` on a machine with
+//! the SDK installed.
+#![cfg(feature = "app")]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use ul::app_core::platform;
+use ul::{
+    Bitmap, Command, Config, GpuDriver, IndexBuffer, Platform, RenderBuffer, Renderer,
+    VertexBuffer, View, ViewConfig,
+};
+
+/// A driver that draws nothing and records which callbacks fired.
+#[derive(Default)]
+struct RecordingDriver {
+    calls: Rc<RefCell<Vec<&'static str>>>,
+    next_id: u32,
+}
+
+impl RecordingDriver {
+    fn record(&self, call: &'static str) {
+        self.calls.borrow_mut().push(call);
+    }
+
+    fn next_id(&mut self) -> u32 {
+        self.next_id += 1;
+        self.next_id
+    }
+}
+
+impl GpuDriver for RecordingDriver {
+    fn next_texture_id(&mut self) -> u32 {
+        self.next_id()
+    }
+
+    fn create_texture(&mut self, _texture_id: u32, _bitmap: &Bitmap) {
+        self.record("create_texture");
+    }
+
+    fn update_texture(&mut self, _texture_id: u32, _bitmap: &Bitmap) {
+        self.record("update_texture");
+    }
+
+    fn destroy_texture(&mut self, _texture_id: u32) {
+        self.record("destroy_texture");
+    }
+
+    fn next_render_buffer_id(&mut self) -> u32 {
+        self.next_id()
+    }
+
+    fn create_render_buffer(&mut self, _render_buffer_id: u32, _buffer: RenderBuffer) {
+        self.record("create_render_buffer");
+    }
+
+    fn destroy_render_buffer(&mut self, _render_buffer_id: u32) {
+        self.record("destroy_render_buffer");
+    }
+
+    fn next_geometry_id(&mut self) -> u32 {
+        self.next_id()
+    }
+
+    fn create_geometry(
+        &mut self,
+        _geometry_id: u32,
+        _vertices: VertexBuffer,
+        _indices: IndexBuffer,
+    ) {
+        self.record("create_geometry");
+    }
+
+    fn update_geometry(
+        &mut self,
+        _geometry_id: u32,
+        _vertices: VertexBuffer,
+        _indices: IndexBuffer,
+    ) {
+        self.record("update_geometry");
+    }
+
+    fn destroy_geometry(&mut self, _geometry_id: u32) {
+        self.record("destroy_geometry");
+    }
+
+    fn update_command_list(&mut self, commands: &[Command]) {
+        if !commands.is_empty() {
+            self.record("update_command_list");
+        }
+    }
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn rendering_an_accelerated_view_drives_the_gpu_driver() {
+    let driver = RecordingDriver::default();
+    let calls = Rc::clone(&driver.calls);
+    Platform::set_gpu_driver(driver);
+    platform::enable_platform_font_loader();
+
+    let renderer = Renderer::new(Config::new());
+    let mut config = ViewConfig::new();
+    config.set_is_accelerated(true);
+    let view = View::new(&renderer, 64, 64, &config, None);
+    view.load_html("<body style='background: red'>Hello</body>");
+    for _ in 0..10 {
+        renderer.update();
+        renderer.render();
+    }
+
+    let calls = calls.borrow();
+    assert!(calls.contains(&"create_render_buffer"), "{calls:?}");
+    assert!(calls.contains(&"create_geometry"), "{calls:?}");
+    assert!(calls.contains(&"update_command_list"), "{calls:?}");
+}
@@ -0,0 +1,22 @@
+//! Checks that the crate builds for headless rendering without the `app` feature.
+//!
+//! The guard below limits this target to `--no-default-features` builds, where
+//! `app_core` and the AppCore library are left out. Run it with
+//! `cargo test --no-default-features --test headless`.
+#![cfg(not(feature = "app"))]
+
+use std::path::Path;
+use ul::javascript_core::{GlobalContext, LogLevel};
+use ul::{Config, Error, Platform, Renderer, View, ViewConfig};
+
+#[test]
+fn renderer_and_javascript_core_are_available_without_app_core() {
+    // Referencing the functions is enough to check they build and link without
+    // AppCore, without needing the Ultralight libraries at runtime
+    let _: fn(Config) -> Result<Renderer, Error> = Renderer::try_new;
+    let _: fn(&str, u32, u32, &Path) -> Result<(), Error> = ul::render_html_to_png;
+    let _: fn(&Renderer, u32, u32, &ViewConfig, Option<&ul::Session>) -> View = View::new;
+    let _: fn(ul::ffi::ULLogger) = Platform::set_logger;
+    let _: fn() -> GlobalContext = GlobalContext::new;
+    assert_ne!(LogLevel::Log, LogLevel::Error);
+}
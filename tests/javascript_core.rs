@@ -0,0 +1,688 @@
+//! Tests that run JavaScript through JavaScriptCore.
+//!
+//! These need the Ultralight libraries at runtime, so they are ignored by default.
+//! Run them with `cargo test -- --ignored` on a machine with the SDK installed.
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::os::raw::c_void;
+use std::rc::Rc;
+use std::time::{Duration, UNIX_EPOCH};
+use ul::javascript_core::{
+    ArrayBuffer, Class, ClassDefinition, Context, ContextGroup, Date, Error, GlobalContext,
+    HashableValue, LogLevel, Object, PropertyAttributes, ProtectedValue, RegExp, StaticValue,
+    String, TypedArray, TypedArrayType, TypedFunction, Value, WeakValue,
+};
+
+fn round_trip<T: Copy + PartialEq + std::fmt::Debug + 'static>(values: &[T]) {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    let array = TypedArray::from_slice(&context, values).unwrap();
+    assert_eq!(Some(array.array_type()), TypedArrayType::for_rust::<T>());
+    assert_eq!(array.to_vec::<T>().unwrap(), values);
+
+    let reversed: Vec<T> = values.iter().rev().copied().collect();
+    array.copy_from_slice(&reversed).unwrap();
+    assert_eq!(array.to_vec::<T>().unwrap(), reversed);
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn typed_arrays_round_trip_every_element_type() {
+    round_trip::<i8>(&[-128, 0, 127]);
+    round_trip::<u8>(&[0, 1, 255]);
+    round_trip::<i16>(&[i16::MIN, 0, i16::MAX]);
+    round_trip::<u16>(&[0, 1, u16::MAX]);
+    round_trip::<i32>(&[i32::MIN, 0, i32::MAX]);
+    round_trip::<u32>(&[0, 1, u32::MAX]);
+    round_trip::<f32>(&[-1.5, 0.0, 3.25]);
+    round_trip::<f64>(&[-1.5, 0.0, f64::MAX]);
+    round_trip::<i64>(&[i64::MIN, 0, i64::MAX]);
+    round_trip::<u64>(&[0, 1, u64::MAX]);
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn typed_arrays_reject_mismatched_element_types() {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    // Same element size, different type
+    let array = TypedArray::from_slice(&context, &[1.0f32, 2.0]).unwrap();
+    assert!(matches!(
+        array.to_vec::<u32>(),
+        Err(Error::ConversionError(_))
+    ));
+    assert!(matches!(
+        array.copy_from_slice(&[1i32, 2]),
+        Err(Error::ConversionError(_))
+    ));
+
+    // u8 reads clamped arrays too
+    let clamped = TypedArray::new(&context, TypedArrayType::Uint8ClampedArray, 2).unwrap();
+    clamped.copy_from_slice(&[7u8, 9]).unwrap();
+    assert_eq!(clamped.to_vec::<u8>().unwrap(), [7, 9]);
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn array_buffer_from_vec_keeps_its_bytes() {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    let buffer = ArrayBuffer::from_vec(&context, vec![1, 2, 3, 4]).unwrap();
+    assert_eq!(buffer.byte_length().unwrap(), 4);
+    assert_eq!(unsafe { buffer.as_slice() }.unwrap(), [1, 2, 3, 4]);
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn drain_microtasks_runs_promise_reactions() {
+    let global = GlobalContext::new();
+    global
+        .evaluate_script(
+            "globalThis.ran = false; Promise.resolve().then(() => { globalThis.ran = true; });",
+            None,
+            0,
+        )
+        .unwrap();
+
+    global.drain_microtasks();
+    assert!(
+        global
+            .global_object()
+            .get_property("ran")
+            .unwrap()
+            .to_boolean()
+    );
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn hashable_values_treat_nan_as_one_key() {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    let mut keys = HashSet::new();
+    keys.insert(HashableValue(Value::number(&context, f64::NAN)));
+    keys.insert(HashableValue(Value::number(&context, f64::NAN)));
+    keys.insert(HashableValue(Value::number(&context, 0.0)));
+    keys.insert(HashableValue(Value::number(&context, -0.0)));
+    assert_eq!(keys.len(), 2);
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn hashable_values_key_objects_by_identity() {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    let first = Object::new(&context).to_value();
+    let second = Object::new(&context).to_value();
+    let alias = context
+        .evaluate_script("(function (o) { return o; })", None, None, 1)
+        .unwrap();
+    let alias = alias
+        .to_object()
+        .unwrap()
+        .call(None, std::slice::from_ref(&first))
+        .unwrap();
+
+    let mut names = HashMap::new();
+    names.insert(HashableValue(first.clone()), "first");
+    names.insert(HashableValue(second.clone()), "second");
+    names.insert(HashableValue(alias.clone()), "alias");
+    assert_eq!(names.len(), 2);
+    assert_eq!(names[&HashableValue(first)], "alias");
+    assert_eq!(names[&HashableValue(second)], "second");
+}
+
+/// Counts how often it is dropped.
+struct DropCounter(Rc<Cell<usize>>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+/// A class whose `answer` property is 42, with callbacks that own `counter`.
+fn answer_class(counter: DropCounter) -> Class {
+    Class::new(ClassDefinition {
+        class_name: String::new("Answer"),
+        get_property: Some(Box::new(move |context, _object, name| {
+            let _ = &counter;
+            if name == "answer" {
+                Ok(Value::number(context, 42.0))
+            } else {
+                Ok(Value::undefined(context))
+            }
+        })),
+        ..ClassDefinition::default()
+    })
+    .unwrap()
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn class_callbacks_are_dropped_once_with_the_class_and_its_objects() {
+    let drops = Rc::new(Cell::new(0));
+    {
+        let global = GlobalContext::new();
+        let context = global.context();
+
+        let class = answer_class(DropCounter(drops.clone()));
+        let first = Object::with_class(&context, &class, None);
+        let second = Object::with_class(&context, &class, None);
+        drop(class);
+        assert_eq!(
+            first.get_property("answer").unwrap().to_number().unwrap(),
+            42.0
+        );
+        assert_eq!(
+            second.get_property("answer").unwrap().to_number().unwrap(),
+            42.0
+        );
+        assert_eq!(drops.get(), 0);
+    }
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn class_callbacks_ignore_private_data_supplied_by_the_caller() {
+    let drops = Rc::new(Cell::new(0));
+    let mut private_data = 7u8;
+    {
+        let global = GlobalContext::new();
+        let context = global.context();
+
+        let class = answer_class(DropCounter(drops.clone()));
+        let object = Object::with_class(
+            &context,
+            &class,
+            Some(&mut private_data as *mut u8 as *mut c_void),
+        );
+        assert!(object.get_property("answer").unwrap().is_undefined());
+    }
+    assert_eq!(private_data, 7);
+    assert_eq!(drops.get(), 1);
+}
+
+/// A `Point` class whose `x` accessor reads a private property.
+fn point_class() -> Class {
+    Class::new(ClassDefinition {
+        class_name: String::new("Point"),
+        static_values: vec![StaticValue {
+            name: String::new("x"),
+            getter: Some(Box::new(|context, object, _name| {
+                Ok(object
+                    .get_private_property("x")?
+                    .unwrap_or_else(|| Value::undefined(context)))
+            })),
+            setter: None,
+            attributes: PropertyAttributes::READ_ONLY,
+        }],
+        ..ClassDefinition::default()
+    })
+    .unwrap()
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn point_constructor_builds_objects_in_script() {
+    let drops = Rc::new(Cell::new(0));
+    {
+        let global = GlobalContext::new();
+        let context = global.context();
+
+        let class = Rc::new(point_class());
+        let counter = DropCounter(drops.clone());
+        let point_class = class.clone();
+        let constructor = class.make_constructor(
+            &context,
+            Box::new(move |context, _constructor, arguments| {
+                let _ = &counter;
+                let point = Object::with_class(context, &point_class, None);
+                let x = arguments
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| Value::number(context, 0.0));
+                point.set_private_property("x", Some(x))?;
+                Ok(point)
+            }),
+        );
+        context
+            .global_object()
+            .set_property("Point", constructor.to_value(), PropertyAttributes::NONE)
+            .unwrap();
+
+        let result = context
+            .evaluate_script(
+                "const p = new Point(3); [p instanceof Point, p.x, new Point().x].join()",
+                None,
+                None,
+                1,
+            )
+            .unwrap();
+        assert_eq!(result.to_string().unwrap().to_string(), "true,3,0");
+        assert_eq!(drops.get(), 0);
+    }
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn global_regexp_matches_several_times() {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    let digits = RegExp::new(&context, r"\d+", "g").unwrap();
+    let input = "a1 b22 c333";
+    assert_eq!(digits.exec(input).unwrap(), Some(vec!["1".to_owned()]));
+    assert_eq!(digits.exec(input).unwrap(), Some(vec!["22".to_owned()]));
+    assert_eq!(digits.exec(input).unwrap(), Some(vec!["333".to_owned()]));
+    // A global regex starts over after its last match
+    assert_eq!(digits.exec(input).unwrap(), None);
+    assert!(digits.test(input).unwrap());
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn regexp_does_not_match_other_input() {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    let digits = RegExp::new(&context, r"(\d+)-(\d+)", "").unwrap();
+    assert!(!digits.test("no digits here").unwrap());
+    assert_eq!(digits.exec("no digits here").unwrap(), None);
+    assert_eq!(
+        digits.exec("call 555-1234").unwrap(),
+        Some(vec![
+            "555-1234".to_owned(),
+            "555".to_owned(),
+            "1234".to_owned()
+        ])
+    );
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn dates_round_trip_system_times() {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    // 2021-01-01T00:00:00.123Z and 1969-07-20T20:17:40Z
+    let after = UNIX_EPOCH + Duration::from_millis(1_609_459_200_123);
+    let before = UNIX_EPOCH - Duration::from_millis(14_182_940_000);
+    for time in [after, before, UNIX_EPOCH] {
+        let date = Date::from_system_time(&context, time).unwrap();
+        assert_eq!(date.to_system_time().unwrap(), time);
+    }
+
+    let before = Date::from_system_time(&context, before).unwrap();
+    assert_eq!(before.timestamp().unwrap(), -14_182_940_000.0);
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn strings_count_code_units_chars_and_bytes() {
+    let string = String::new("😀");
+    assert_eq!(string.len(), 2);
+    assert_eq!(string.chars().count(), 1);
+    assert_eq!(string.chars().next(), Some('😀'));
+    assert_eq!(string.byte_len(), 4);
+
+    // A lone surrogate decodes to the replacement character
+    let lone = String::from_chars(&[0xD83D, u16::from(b'a')]);
+    assert_eq!(lone.chars().collect::<std::string::String>(), "\u{FFFD}a");
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn typed_functions_check_their_arguments() {
+    let global = GlobalContext::new();
+    let context = global.context();
+    let add = TypedFunction::new(&context, |a: f64, b: f64| -> f64 { a + b });
+
+    let sum = add
+        .call(
+            None,
+            &[Value::number(&context, 1.5), Value::number(&context, 2.0)],
+        )
+        .unwrap();
+    assert_eq!(sum.to_number().unwrap(), 3.5);
+
+    let too_few = add.call(None, &[Value::number(&context, 1.5)]).unwrap_err();
+    assert!(too_few.is_js_exception());
+    assert!(too_few.message().contains("Too few arguments"), "{too_few}");
+
+    let wrong_type = add
+        .call(
+            None,
+            &[Value::number(&context, 1.5), Value::string(&context, "2")],
+        )
+        .unwrap_err();
+    assert!(wrong_type.is_js_exception());
+    assert!(
+        wrong_type.message().contains("Expected a number"),
+        "{wrong_type}"
+    );
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn accessor_getters_are_called_from_javascript() {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    let reads = Rc::new(Cell::new(0.0));
+    let getter = TypedFunction::new(&context, {
+        let reads = Rc::clone(&reads);
+        move || -> f64 {
+            reads.set(reads.get() + 1.0);
+            reads.get()
+        }
+    });
+
+    let counter = Object::new(&context);
+    counter
+        .define_accessor(
+            "value",
+            Some(getter.to_value()),
+            None,
+            PropertyAttributes::NONE,
+        )
+        .unwrap();
+    context
+        .global_object()
+        .set_property("counter", counter.to_value(), PropertyAttributes::NONE)
+        .unwrap();
+
+    let values = context
+        .evaluate_script("[counter.value, counter.value].join()", None, None, 1)
+        .unwrap();
+    assert_eq!(values.to_string().unwrap().to_string(), "1,2");
+    assert_eq!(reads.get(), 2.0);
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn values_are_shared_only_within_a_context_group() {
+    let group = ContextGroup::new();
+    let first = group.create_global_context(None);
+    let second = group.create_global_context(None);
+    let other = GlobalContext::new();
+    let (first, second, other) = (first.context(), second.context(), other.context());
+    assert!(first.shares_group_with(&second));
+    assert!(!first.shares_group_with(&other));
+
+    let point = first.evaluate_script("({ x: 3 })", None, None, 1).unwrap();
+    let shared = point.transfer_to(&second).unwrap();
+    second
+        .global_object()
+        .set_property("point", shared, PropertyAttributes::NONE)
+        .unwrap();
+    let x = second.evaluate_script("point.x", None, None, 1).unwrap();
+    assert_eq!(x.to_number().unwrap(), 3.0);
+
+    assert!(matches!(
+        point.transfer_to(&other),
+        Err(Error::InvalidParameter(_))
+    ));
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn rust_globals_are_called_from_javascript() {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    context
+        .define_function("add", |context, _function, _this, arguments| {
+            let a = arguments[0].to_number()?;
+            let b = arguments[1].to_number()?;
+            Ok(Value::number(context, a + b))
+        })
+        .unwrap();
+    context
+        .set_global("offset", Value::number(&context, 10.0))
+        .unwrap();
+
+    let sum = context
+        .evaluate_script("add(2, 3) + offset", None, None, 1)
+        .unwrap();
+    assert_eq!(sum.to_number().unwrap(), 15.0);
+    assert!(context.get_global("add").unwrap().is_object());
+    assert!(context.get_global("missing").unwrap().is_undefined());
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn own_property_keys_include_symbols_and_non_enumerable_properties() {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    let object = Object::new(&context);
+    let symbol = Value::symbol(&context, Some("tag"));
+    object
+        .set_property(
+            "visible",
+            Value::number(&context, 1.0),
+            PropertyAttributes::NONE,
+        )
+        .unwrap();
+    object
+        .define_value_property("hidden", Value::number(&context, 2.0), true, false, true)
+        .unwrap();
+    object
+        .set_property_for_key(
+            symbol.clone(),
+            Value::number(&context, 3.0),
+            PropertyAttributes::NONE,
+        )
+        .unwrap();
+
+    let keys = object.own_property_keys().unwrap();
+    assert_eq!(keys.len(), 3);
+    assert_eq!(keys[0].to_string().unwrap().to_string(), "visible");
+    assert_eq!(keys[1].to_string().unwrap().to_string(), "hidden");
+    assert!(keys[2].is_symbol());
+    assert!(keys[2].strict_equals(&symbol));
+
+    let names: Vec<std::string::String> = object
+        .get_property_names()
+        .unwrap()
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    assert_eq!(names, ["visible"]);
+}
+
+/// Collect garbage until `done` returns true, giving up after a few attempts.
+///
+/// JSGarbageCollect only asks for a collection, so this allocates and retries
+/// until one has run.
+fn collect_garbage_until(global: &GlobalContext, mut done: impl FnMut() -> bool) -> bool {
+    for _ in 0..50 {
+        global
+            .evaluate_script("for (let i = 0; i < 100000; i++) ({ i });", None, 1)
+            .unwrap();
+        global.garbage_collect();
+        if done() {
+            return true;
+        }
+    }
+    false
+}
+
+/// Create an object that only a weak reference points to.
+///
+/// This is kept out of line so that no pointer to the object is left on the
+/// caller's stack, where JavaScriptCore's conservative stack scan would find it.
+#[inline(never)]
+fn unreferenced_object<'a>(context: &Context<'a>) -> WeakValue<'a> {
+    let object = context.evaluate_script("({ big: new Array(1000).fill(1) })", None, None, 1);
+    object.unwrap().downgrade().unwrap()
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn weak_values_go_away_after_garbage_collection() {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    let weak = unreferenced_object(&context);
+    let kept = context
+        .evaluate_script("globalThis.kept = {}; kept", None, None, 1)
+        .unwrap()
+        .downgrade()
+        .unwrap();
+
+    assert!(
+        collect_garbage_until(&global, || weak.upgrade(&context).is_none()),
+        "the unreferenced object was never collected"
+    );
+    assert!(kept.upgrade(&context).is_some());
+}
+
+/// Create two objects in a scope and escape the second, returning weak references
+/// to both and the escaped value.
+///
+/// Kept out of line for the same reason as `unreferenced_object`.
+#[inline(never)]
+fn scoped_objects<'a>(context: &Context<'a>) -> (WeakValue<'a>, WeakValue<'a>, ProtectedValue<'a>) {
+    context.scope(|scope| {
+        let dropped = scope.object().to_value();
+        let escaped = scope.object().to_value();
+        (
+            dropped.downgrade().unwrap(),
+            escaped.downgrade().unwrap(),
+            scope.escape(escaped),
+        )
+    })
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn only_escaped_values_outlive_their_scope() {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    let (dropped, escaped, protected) = scoped_objects(&context);
+
+    assert!(
+        collect_garbage_until(&global, || dropped.upgrade(&context).is_none()),
+        "the value left in the scope was never collected"
+    );
+    assert!(
+        escaped
+            .upgrade(&context)
+            .unwrap()
+            .strict_equals(protected.get())
+    );
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn execution_time_limit_stops_an_endless_loop() {
+    let group = ContextGroup::new();
+    group.set_execution_time_limit(0.1, None);
+    let global = group.create_global_context(None);
+
+    let started = std::time::Instant::now();
+    let result = global.evaluate_script("while (true) {}", None, 1);
+    assert!(
+        matches!(&result, Err(Error::JSError(message)) if message == "execution timed out"),
+        "{result:?}"
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    // The context is still usable after a script was terminated
+    let value = global.evaluate_script("1 + 1", None, 1).unwrap();
+    assert_eq!(value.to_number().unwrap(), 2.0);
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn map_entries_are_read_in_insertion_order() {
+    let global = GlobalContext::new();
+    let map = global
+        .evaluate_script("new Map([['b', 2], ['a', 1]])", None, 1)
+        .unwrap();
+    assert!(map.is_map());
+    assert!(!map.is_set());
+
+    let entries: Vec<(std::string::String, f64)> = map
+        .to_object()
+        .unwrap()
+        .map_entries()
+        .unwrap()
+        .into_iter()
+        .map(|(key, value)| {
+            (
+                key.to_string().unwrap().to_string(),
+                value.to_number().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(entries, [("b".to_owned(), 2.0), ("a".to_owned(), 1.0)]);
+
+    let set = global.evaluate_script("new Set([1])", None, 1).unwrap();
+    assert!(set.is_set());
+    assert!(!set.is_map());
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn installed_console_forwards_messages() {
+    let global = GlobalContext::new();
+    let messages = Rc::new(RefCell::new(Vec::new()));
+    global
+        .install_console({
+            let messages = Rc::clone(&messages);
+            move |level, text| messages.borrow_mut().push((level, text.to_owned()))
+        })
+        .unwrap();
+
+    global
+        .evaluate_script("console.log(\"hi\", 42); console.error(\"boom\");", None, 1)
+        .unwrap();
+    assert_eq!(
+        *messages.borrow(),
+        [
+            (LogLevel::Log, "hi 42".to_owned()),
+            (LogLevel::Error, "boom".to_owned()),
+        ]
+    );
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn methods_are_invoked_with_the_object_as_this() {
+    let global = GlobalContext::new();
+    let context = global.context();
+
+    let array = global
+        .evaluate_script("[1, 2]", None, 1)
+        .unwrap()
+        .to_object()
+        .unwrap();
+    let tail = global.evaluate_script("[3]", None, 1).unwrap();
+    let joined = array
+        .invoke("concat", &[tail, Value::number(&context, 4.0)])
+        .unwrap();
+    let numbers: Vec<f64> = joined
+        .to_vec()
+        .unwrap()
+        .iter()
+        .map(|value| value.to_number().unwrap())
+        .collect();
+    assert_eq!(numbers, [1.0, 2.0, 3.0, 4.0]);
+
+    assert!(matches!(
+        array.invoke("length", &[]),
+        Err(Error::InvalidType(_))
+    ));
+}
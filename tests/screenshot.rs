@@ -0,0 +1,31 @@
+//! Tests that render HTML to images.
+//!
+//! These need the Ultralight libraries and resources at runtime, so they are
+//! ignored by default. Run them with `cargo test -- --ignored` on a machine with
+//! the SDK installed.
+#![cfg(feature = "app")]
+
+use std::fs;
+use ul::app_core::platform;
+
+const RED_DIV: &str = r#"<html><body style="margin: 0; background: white">
+<div style="position: absolute; left: 25%; top: 25%; width: 50%; height: 50%; background: #ff0000"></div>
+</body></html>"#;
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn rendering_html_draws_its_content() {
+    platform::enable_platform_font_loader();
+    platform::enable_platform_file_system(".");
+
+    let bitmap = ul::render_html_to_bitmap(RED_DIV, 80, 60).unwrap();
+    assert_eq!((bitmap.width(), bitmap.height()), (80, 60));
+    assert_eq!(bitmap.get_pixel(40, 30), Some([0xFF, 0x00, 0x00, 0xFF]));
+    assert_eq!(bitmap.get_pixel(2, 2), Some([0xFF, 0xFF, 0xFF, 0xFF]));
+
+    let path = std::env::temp_dir().join(format!("ul-screenshot-{}.png", std::process::id()));
+    ul::render_html_to_png(RED_DIV, 80, 60, &path).unwrap();
+    let png = fs::read(&path).unwrap();
+    let _ = fs::remove_file(&path);
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+}
@@ -0,0 +1,36 @@
+//! Tests for the fallible string constructors.
+//!
+//! Most of these need the Ultralight libraries at runtime, so they are ignored by
+//! default. Run them with `cargo test -- --ignored` on a machine with the SDK installed.
+
+use ul::javascript_core;
+
+#[test]
+fn ul_try_from_str_rejects_null_bytes() {
+    assert!(matches!(
+        ul::String::try_from_str("a\0b"),
+        Err(ul::Error::InvalidArgument(_))
+    ));
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn ul_try_from_str_keeps_the_whole_string() {
+    let string = ul::String::try_from_str("héllo").unwrap();
+    assert_eq!(string.as_str().unwrap(), "héllo");
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn javascript_core_try_from_str_keeps_null_bytes() {
+    let string = javascript_core::String::try_from_str("a\0b").unwrap();
+    assert_eq!(string.len(), 3);
+    assert_eq!(string.to_chars(), [u16::from(b'a'), 0, u16::from(b'b')]);
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn javascript_core_try_from_str_matches_new_without_null_bytes() {
+    let string = javascript_core::String::try_from_str("héllo").unwrap();
+    assert!(string.equals(&javascript_core::String::new("héllo")));
+}
@@ -0,0 +1,95 @@
+//! Tests that drive a real View.
+//!
+//! These need the Ultralight libraries and resources at runtime, so they are
+//! ignored by default. Run them with `cargo test -- --ignored` on a machine with
+//! the SDK installed. Ultralight allows one renderer at a time, so the tests
+//! take turns creating theirs.
+#![cfg(feature = "app")]
+
+use std::sync::{Mutex, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
+use ul::view::FinishLoadingCallback;
+use ul::app_core::platform;
+use ul::{Config, Error, LoadState, Renderer, View, ViewConfig};
+
+fn with_view(f: impl FnOnce(&Renderer, &View)) {
+    static RENDERER_LOCK: Mutex<()> = Mutex::new(());
+    let _guard = RENDERER_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    platform::enable_platform_font_loader();
+    platform::enable_platform_file_system(".");
+    let renderer = Renderer::new(Config::new());
+    let view = View::new(&renderer, 200, 200, &ViewConfig::new(), None);
+    f(&renderer, &view);
+}
+
+/// Load `html` into the view and update the renderer until the main frame is loaded.
+fn load_html(renderer: &Renderer, view: &View, html: &str) {
+    view.load_html(html);
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while view.load_state() != LoadState::Loaded {
+        assert!(Instant::now() < deadline, "timed out loading the page");
+        renderer.update();
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn throwing_script_surfaces_a_line_number() {
+    with_view(|_renderer, view| {
+        let error = view
+            .evaluate_script_with("let x = 1;\n\nthrow new Error('boom');", Some("test.js"), 1)
+            .unwrap_err();
+        let Error::JavaScriptException {
+            message,
+            source_url,
+            line,
+            ..
+        } = error
+        else {
+            panic!("expected a JavaScript exception, got {:?}", error);
+        };
+        assert!(message.contains("boom"), "unexpected message {:?}", message);
+        assert_eq!(source_url.as_deref(), Some("test.js"));
+        assert_eq!(line, Some(3));
+    });
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn evaluate_script_keeps_reporting_javascript_error() {
+    with_view(|_renderer, view| {
+        let error = view.evaluate_script("throw new Error('boom')").unwrap_err();
+        assert!(
+            matches!(error, Error::JavaScriptError(_)),
+            "got {:?}",
+            error
+        );
+    });
+}
+
+/// Reports what the view passed to the finish loading callback sees.
+struct ReportLoadState(mpsc::Sender<(LoadState, usize)>);
+
+impl FinishLoadingCallback for ReportLoadState {
+    fn on_finish_loading(&self, view: &View, _frame_id: u64, is_main_frame: bool, _url: &str) {
+        if is_main_frame {
+            let _ = self.0.send((view.load_state(), view.tracked_history_len()));
+        }
+    }
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries"]
+fn callbacks_see_the_state_of_their_view() {
+    with_view(|renderer, view| {
+        let (sender, receiver) = mpsc::channel();
+        view.set_finish_loading_callback(ReportLoadState(sender));
+        load_html(renderer, view, "<p>Hello</p>");
+        assert_eq!(receiver.try_recv(), Ok((LoadState::Loaded, 1)));
+    });
+}
@@ -0,0 +1,65 @@
+//! Tests that open an AppCore window.
+//!
+//! These need the Ultralight libraries and a display at runtime, so they are
+//! ignored by default. Run them with `cargo test -- --ignored` on a machine with
+//! the SDK installed. AppCore allows one App at a time, so the tests take turns
+//! creating theirs.
+#![cfg(feature = "app")]
+
+use std::sync::Mutex;
+use ul::Config;
+use ul::app_core::{App, Cursor, Settings, Window, WindowFlags};
+
+fn with_window(f: impl FnOnce(&Window)) {
+    static APP_LOCK: Mutex<()> = Mutex::new(());
+    let _guard = APP_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let app = App::new(&Settings::new().unwrap(), &Config::new()).unwrap();
+    let window = Window::new(
+        &app.main_monitor().unwrap(),
+        400,
+        300,
+        false,
+        WindowFlags::TITLED,
+    )
+    .unwrap();
+    f(&window);
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries and a display"]
+fn client_sizes_are_related_by_the_scale() {
+    with_window(|window| {
+        let (pixel_width, pixel_height) = window.client_size_pixels();
+        let (screen_width, screen_height) = window.client_size_screen();
+        let scale = window.scale();
+        assert!(scale > 0.0);
+        assert_eq!(
+            (pixel_width, pixel_height),
+            (window.width(), window.height())
+        );
+
+        // The screen size is rounded, so allow up to one screen unit of difference
+        assert!((f64::from(screen_width) * scale - f64::from(pixel_width)).abs() <= scale);
+        assert!((f64::from(screen_height) * scale - f64::from(pixel_height)).abs() <= scale);
+    });
+}
+
+#[test]
+#[ignore = "requires the Ultralight libraries and a display"]
+fn windows_accept_common_cursors() {
+    with_window(|window| {
+        for cursor in [
+            Cursor::kCursor_Pointer,
+            Cursor::kCursor_Hand,
+            Cursor::kCursor_IBeam,
+            Cursor::kCursor_Wait,
+            Cursor::kCursor_Cross,
+        ] {
+            window.set_cursor(cursor);
+            window.apply_view_cursor(cursor);
+        }
+    });
+}